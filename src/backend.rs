@@ -0,0 +1,111 @@
+use piston_window::{*, types::FontSize};
+
+use crate::{draw::*, font::FontSource, text::*, Error};
+
+/// A backend provides the terminal with events and draws the frames the terminal describes.
+/// 
+/// The terminal's `show`, `tell`, `ask`, and `display_art` logic is written against this trait,
+/// so the same script can run in a real window or in an offscreen buffer.
+pub trait Backend {
+    /// Returns the next event, or None if the backend has been closed.
+    fn next_event(&mut self) -> Option<Event>;
+
+    /// Returns the current size of the drawing area.
+    fn size(&self) -> Size;
+
+    /// Loads the given font. The font will be used to draw every following frame.
+    fn load_font(&mut self, font: &FontSource) -> Result<(), Error>;
+
+    /// Returns the width in pixels of the given text, drawn with the loaded font at the given size.
+    fn text_width(&mut self, text: &str, font_size: FontSize) -> Result<f64, Error>;
+
+    /// Draws the given frame if the given event is a render event.
+    fn render(&mut self, event: &Event, frame: &Frame) -> Result<(), Error>;
+
+    /// Changes the size of the drawing area. The loaded font may be dropped, so load it again afterwards.
+    fn resize(&mut self, new_size: Size) -> Result<(), Error>;
+}
+
+/// A backend that draws the terminal in a PistonWindow.
+pub struct WindowBackend {
+    settings: WindowSettings,
+    /// The window that displays our terminal.
+    pub window: PistonWindow,
+    glyphs: Option<Glyphs>,
+}
+
+impl WindowBackend {
+    /// Creates a new window with the given title and size.
+    /// 
+    /// ```no_run
+    /// # use simpleterm::backend::WindowBackend;
+    /// let backend: WindowBackend = WindowBackend::new("simpleterm test", (800, 600).into()).unwrap();
+    /// ```
+    pub fn new(title: &str, size: Size) -> Result<WindowBackend, Error> {
+        WindowBackend::from_settings(WindowSettings::new(title, size).exit_on_esc(true))
+    }
+
+    /// Creates a new window from the given settings. The same settings are used when the window is resized.
+    /// 
+    /// ```no_run
+    /// # use piston_window::WindowSettings;
+    /// # use simpleterm::backend::WindowBackend;
+    /// let settings: WindowSettings = WindowSettings::new("simpleterm test", (800, 600)).exit_on_esc(false);
+    /// let backend: WindowBackend = WindowBackend::from_settings(settings).unwrap();
+    /// ```
+    pub fn from_settings(settings: WindowSettings) -> Result<WindowBackend, Error> {
+        Ok(WindowBackend {
+            window: build_window(&settings)?,
+            settings,
+            glyphs: None,
+        })
+    }
+}
+
+impl Backend for WindowBackend {
+    fn next_event(&mut self) -> Option<Event> {
+        self.window.next()
+    }
+
+    fn size(&self) -> Size {
+        self.window.window.size()
+    }
+
+    fn load_font(&mut self, font: &FontSource) -> Result<(), Error> {
+        self.glyphs = Some(load_font(&mut self.window, font)?);
+        Ok(())
+    }
+
+    fn text_width(&mut self, text: &str, font_size: FontSize) -> Result<f64, Error> {
+        match self.glyphs.as_mut() {
+            Some(glyphs) => measure_text(glyphs, text, font_size),
+            None => Err(Error::Render(String::from("no font is loaded"))),
+        }
+    }
+
+    fn render(&mut self, event: &Event, frame: &Frame) -> Result<(), Error> {
+        let win_size: Size = self.size();
+
+        match self.glyphs.as_mut() {
+            Some(glyphs) => self.window.draw_2d(event, |c, g, device| {
+                let result: Result<(), Error> = draw_frame(frame, win_size, glyphs, c, g);
+                glyphs.factory.encoder.flush(device);
+                result
+            }).unwrap_or(Ok(())),
+            None => Ok(()),
+        }
+    }
+
+    // The glyph cache belongs to the old window, so it is dropped along with it.
+    fn resize(&mut self, new_size: Size) -> Result<(), Error> {
+        self.settings.set_size(new_size);
+        self.window = build_window(&self.settings)?;
+        self.glyphs = None;
+        Ok(())
+    }
+}
+
+// Opens a new PistonWindow with the given settings.
+fn build_window(settings: &WindowSettings) -> Result<PistonWindow, Error> {
+    settings.build().map_err(|e| Error::WindowCreation(e.to_string()))
+}
@@ -0,0 +1,275 @@
+use piston_window::{Key, Size, WindowSettings, types::{Color, FontSize}};
+use std::{path::PathBuf, time::Duration};
+
+use crate::{backend::*, font::*, resources::ResourcePath, terminal::Terminal, text::*, Error, FLASH_TIME, SCROLLBACK_LINES, TEXT_OFFSET, TYPE_TIME};
+
+/// Collects a terminal's settings one at a time, then checks them and creates the terminal.
+///
+/// Every setting has a default, so only the settings that matter to your program need to be given.
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use simpleterm::{builder::TerminalBuilder, text::*};
+/// # use simpleterm::terminal::Terminal;
+/// let mut term: Terminal = TerminalBuilder::new()
+///     .title("simpleterm test")
+///     .size((800, 600))
+///     .colors(DARK_GREY, GOLD)
+///     .font("LeagueSpartan-Regular.ttf", 32)
+///     .art_font("LeagueMono-Regular.ttf", 9)
+///     .type_time(Duration::from_millis(40))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct TerminalBuilder {
+    title: String,
+    size: (u32, u32),
+    bg_color: Color,
+    fg_color: Color,
+    font: FontSource,
+    font_size: FontSize,
+    art_font: FontSource,
+    art_font_size: FontSize,
+    scanlines: bool,
+    type_time: Duration,
+    chars_per_second: Option<f64>,
+    flash_time: Duration,
+    text_offset: (f64, f64),
+    append_mode: bool,
+    scrollback: usize,
+    exit_on_esc: bool,
+    countdown: bool,
+    instant_text: bool,
+    skip_key: Option<Key>,
+    resources: ResourcePath,
+}
+
+impl TerminalBuilder {
+    /// Creates a builder with the default settings:
+    /// an 800x600 window titled "simpleterm" that closes on Escape,
+    /// GOLD on DARK_GREY with scanlines, LeagueSpartan at 32pt for text and LeagueMono at 10pt for art,
+    /// messages that replace each other, and the TYPE_TIME, FLASH_TIME, TEXT_OFFSET, and SCROLLBACK_LINES constants.
+    pub fn new() -> TerminalBuilder {
+        TerminalBuilder::default()
+    }
+
+    /// Sets the title of the window.
+    pub fn title(mut self, title: &str) -> TerminalBuilder {
+        self.title = String::from(title);
+        self
+    }
+
+    /// Sets the (x, y) size of the window.
+    pub fn size(mut self, size: (u32, u32)) -> TerminalBuilder {
+        self.size = size;
+        self
+    }
+
+    /// Sets the background and foreground colors.
+    pub fn colors(mut self, bg: Color, fg: Color) -> TerminalBuilder {
+        self.bg_color = bg;
+        self.fg_color = fg;
+        self
+    }
+
+    /// Sets the font and font size of normal text.
+    pub fn font<F: Into<FontSource>>(mut self, font: F, size: FontSize) -> TerminalBuilder {
+        self.font = font.into();
+        self.font_size = size;
+        self
+    }
+
+    /// Sets the font and font size of ascii art. You probably want a mono-space font here, and a small size.
+    pub fn art_font<F: Into<FontSource>>(mut self, font: F, size: FontSize) -> TerminalBuilder {
+        self.art_font = font.into();
+        self.art_font_size = size;
+        self
+    }
+
+    /// Sets whether or not to draw scanlines.
+    pub fn scanlines(mut self, scanlines: bool) -> TerminalBuilder {
+        self.scanlines = scanlines;
+        self
+    }
+
+    /// Sets how long the terminal takes to type a single character. A zero duration shows each message at once.
+    pub fn type_time(mut self, time: Duration) -> TerminalBuilder {
+        self.type_time = time;
+        self.chars_per_second = None;
+        self
+    }
+
+    /// Sets how many characters the terminal types per second. The rate has to be a finite number above zero.
+    pub fn chars_per_second(mut self, rate: f64) -> TerminalBuilder {
+        self.type_time = type_time_for(rate);
+        self.chars_per_second = Some(rate);
+        self
+    }
+
+    /// Sets how long flashing elements like the input cursor take before toggling their flash state.
+    pub fn flash_time(mut self, time: Duration) -> TerminalBuilder {
+        self.flash_time = time;
+        self
+    }
+
+    /// Sets the x and y offset of the text from the corners of the terminal.
+    pub fn text_offset(mut self, offset: (f64, f64)) -> TerminalBuilder {
+        self.text_offset = offset;
+        self
+    }
+
+    /// Sets whether new messages are added below the previous ones instead of replacing them.
+    pub fn append_mode(mut self, append: bool) -> TerminalBuilder {
+        self.append_mode = append;
+        self
+    }
+
+    /// Sets how many lines of past messages the terminal keeps in append mode.
+    pub fn scrollback(mut self, lines: usize) -> TerminalBuilder {
+        self.scrollback = lines;
+        self
+    }
+
+    /// Sets whether or not pressing Escape closes the window.
+    pub fn exit_on_esc(mut self, exit: bool) -> TerminalBuilder {
+        self.exit_on_esc = exit;
+        self
+    }
+
+    /// Sets whether prompts that time out show how many seconds they have left.
+    pub fn countdown(mut self, countdown: bool) -> TerminalBuilder {
+        self.countdown = countdown;
+        self
+    }
+
+    /// Sets whether messages appear all at once instead of being typed out.
+    pub fn instant_text(mut self, instant: bool) -> TerminalBuilder {
+        self.instant_text = instant;
+        self
+    }
+
+    /// Sets the key that finishes typing out a message right away, or None to let any key do it.
+    pub fn skip_key(mut self, key: Option<Key>) -> TerminalBuilder {
+        self.skip_key = key;
+        self
+    }
+
+    /// Sets the folders the terminal looks for font and art files in.
+    pub fn resources(mut self, resources: ResourcePath) -> TerminalBuilder {
+        self.resources = resources;
+        self
+    }
+
+    /// Adds a folder to the end of the folders the terminal looks for font and art files in.
+    pub fn resource_dir<P: Into<PathBuf>>(mut self, dir: P) -> TerminalBuilder {
+        self.resources.push(dir);
+        self
+    }
+
+    /// Checks the settings, then opens a window and creates the terminal.
+    /// Returns an error if a setting can't be used, a font can't be loaded, or the window can't be created.
+    ///
+    /// ```
+    /// # use simpleterm::{builder::TerminalBuilder, Error};
+    /// assert!(matches!(TerminalBuilder::new().size((0, 600)).build(), Err(Error::InvalidConfig(_))));
+    /// ```
+    pub fn build(self) -> Result<Terminal, Error> {
+        self.validate(self.size.into())?;
+
+        let settings: WindowSettings = WindowSettings::new(self.title.as_str(), self.size).exit_on_esc(self.exit_on_esc);
+        self.build_with_backend(WindowBackend::from_settings(settings)?)
+    }
+
+    /// Checks the settings and creates a terminal displayed by the given backend.
+    /// The title, size, and exit_on_esc settings are left to the backend.
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use simpleterm::{builder::TerminalBuilder, headless::HeadlessBackend, text::*, Error};
+    /// # use simpleterm::terminal::Terminal;
+    /// let mut term: Terminal<HeadlessBackend> = TerminalBuilder::new()
+    ///     .colors(OFF_WHITE, DARK_PURPLE)
+    ///     .font("LeagueMono-Regular.ttf", 16)
+    ///     .scanlines(false)
+    ///     .type_time(Duration::from_millis(0))
+    ///     .text_offset((40.0, 60.0))
+    ///     .build_with_backend(HeadlessBackend::new((320, 240)))
+    ///     .unwrap();
+    /// term.show("Ready.", Duration::from_millis(50));
+    /// assert!(!term.scanlines);
+    ///
+    /// let result = TerminalBuilder::new().art_font("Missing.ttf", 10).build_with_backend(HeadlessBackend::new((320, 240)));
+    /// assert!(matches!(result, Err(Error::FontNotFound(_))));
+    ///
+    /// let result = TerminalBuilder::new().text_offset((200.0, 50.0)).build_with_backend(HeadlessBackend::new((320, 240)));
+    /// assert!(matches!(result, Err(Error::InvalidConfig(_))));
+    ///
+    /// let result = TerminalBuilder::new().chars_per_second(0.0).build_with_backend(HeadlessBackend::new((320, 240)));
+    /// assert!(matches!(result, Err(Error::InvalidConfig(_))));
+    /// ```
+    pub fn build_with_backend<B: Backend>(self, backend: B) -> Result<Terminal<B>, Error> {
+        self.validate(backend.size())?;
+
+        let mut term: Terminal<B> = Terminal::try_with_resources(backend, self.resources, self.bg_color, self.fg_color, self.font, self.font_size)?;
+        term.try_set_art_font(self.art_font, self.art_font_size)?;
+        term.scanlines = self.scanlines;
+        term.type_time = self.type_time;
+        term.flash_time = self.flash_time;
+        term.text_offset = self.text_offset;
+        term.append_mode = self.append_mode;
+        term.scrollback = self.scrollback;
+        term.countdown = self.countdown;
+        term.instant_text = self.instant_text;
+        term.skip_key = self.skip_key;
+        Ok(term)
+    }
+
+    // Returns an error describing the first setting that can't be used with a terminal of the given size.
+    fn validate(&self, size: Size) -> Result<(), Error> {
+        let (x, y): (f64, f64) = self.text_offset;
+
+        if size.width < 1.0 || size.height < 1.0 {
+            Err(Error::InvalidConfig(format!("the terminal must be at least 1x1, not {}x{}", size.width, size.height)))
+        } else if self.font_size == 0 || self.art_font_size == 0 {
+            Err(Error::InvalidConfig(String::from("font sizes must be greater than zero")))
+        } else if self.append_mode && self.scrollback == 0 {
+            Err(Error::InvalidConfig(String::from("the scrollback must keep at least one line in append mode")))
+        } else if self.flash_time == Duration::from_secs(0) {
+            Err(Error::InvalidConfig(String::from("the flash time must be greater than zero")))
+        } else if let Some(rate) = self.chars_per_second.filter(|rate| !(rate.is_finite() && *rate > 0.0)) {
+            Err(Error::InvalidConfig(format!("the terminal must type a finite number of characters per second above zero, not {}", rate)))
+        } else if x < 0.0 || y < 0.0 || x * 2.0 >= size.width || y * 2.0 >= size.height {
+            Err(Error::InvalidConfig(format!("a text offset of ({}, {}) doesn't fit in a {}x{} terminal", x, y, size.width, size.height)))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Default for TerminalBuilder {
+    fn default() -> TerminalBuilder {
+        TerminalBuilder {
+            title: String::from("simpleterm"),
+            size: (800, 600),
+            bg_color: DARK_GREY,
+            fg_color: GOLD,
+            font: EmbeddedFont::LeagueSpartan.into(),
+            font_size: 32,
+            art_font: EmbeddedFont::LeagueMono.into(),
+            art_font_size: 10,
+            scanlines: true,
+            type_time: TYPE_TIME,
+            chars_per_second: None,
+            flash_time: FLASH_TIME,
+            text_offset: TEXT_OFFSET,
+            append_mode: false,
+            scrollback: SCROLLBACK_LINES,
+            exit_on_esc: true,
+            countdown: false,
+            instant_text: false,
+            skip_key: None,
+            resources: ResourcePath::default(),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+/// A clipboard holds text the user has copied, so it can be pasted into a prompt.
+pub trait Clipboard {
+    /// Returns the text on the clipboard, or None if there is no text on it.
+    fn get_text(&mut self) -> Option<String>;
+
+    /// Puts the given text on the clipboard.
+    fn set_text(&mut self, text: &str);
+}
+
+/// A clipboard that only exists inside this program, for testing or for systems without a clipboard.
+///
+/// ```
+/// # use simpleterm::clipboard::*;
+/// let mut clipboard: MemoryClipboard = MemoryClipboard::new();
+/// assert_eq!(clipboard.get_text(), None);
+/// clipboard.set_text("xyzzy");
+/// assert_eq!(clipboard.get_text(), Some(String::from("xyzzy")));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryClipboard {
+    text: Option<String>,
+}
+
+impl MemoryClipboard {
+    /// Creates an empty clipboard.
+    pub fn new() -> MemoryClipboard {
+        MemoryClipboard::default()
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        self.text = Some(String::from(text));
+    }
+}
+
+/// The operating system's clipboard.
+///
+/// If the "system-clipboard" feature is off, or the system clipboard can't be reached
+/// (like on a Linux machine without a display), the clipboard only exists inside this program.
+///
+/// ```
+/// # use simpleterm::clipboard::*;
+/// let mut clipboard: SystemClipboard = SystemClipboard::new();
+/// clipboard.set_text("plugh");
+/// if !clipboard.is_system() {
+///     assert_eq!(clipboard.get_text(), Some(String::from("plugh")));
+/// }
+/// ```
+pub struct SystemClipboard {
+    #[cfg(feature = "system-clipboard")]
+    system: Option<arboard::Clipboard>,
+    memory: MemoryClipboard,
+}
+
+impl SystemClipboard {
+    /// Connects to the operating system's clipboard, or falls back to a clipboard inside this program.
+    pub fn new() -> SystemClipboard {
+        SystemClipboard {
+            #[cfg(feature = "system-clipboard")]
+            system: arboard::Clipboard::new().ok(),
+            memory: MemoryClipboard::new(),
+        }
+    }
+
+    /// Returns whether this clipboard is connected to the operating system's clipboard.
+    pub fn is_system(&self) -> bool {
+        #[cfg(feature = "system-clipboard")]
+        return self.system.is_some();
+        #[cfg(not(feature = "system-clipboard"))]
+        return false;
+    }
+}
+
+impl Default for SystemClipboard {
+    fn default() -> SystemClipboard {
+        SystemClipboard::new()
+    }
+}
+
+impl Clipboard for SystemClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        #[cfg(feature = "system-clipboard")]
+        if let Some(text) = self.system.as_mut().and_then(|system| system.get_text().ok()) {
+            return Some(text);
+        }
+        self.memory.get_text()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        #[cfg(feature = "system-clipboard")]
+        if let Some(system) = self.system.as_mut() {
+            if system.set_text(text).is_ok() { return; }
+        }
+        self.memory.set_text(text);
+    }
+}
+
+/// Joins the lines of the given text with spaces, so pasted text fits on a single input line.
+/// ```
+/// # use simpleterm::clipboard::single_line;
+/// assert_eq!(single_line("open door\r\nenter\n"), "open door enter");
+/// ```
+pub fn single_line(text: &str) -> String {
+    text.lines().collect::<Vec<&str>>().join(" ")
+}
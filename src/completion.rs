@@ -0,0 +1,81 @@
+/// The ways the text before the cursor can be completed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Completion {
+    /// The byte index in the input where the completed text starts. The text from here to the cursor is replaced.
+    pub start: usize,
+    /// The texts that could replace the text between start and the cursor.
+    pub candidates: Vec<String>,
+}
+
+impl Completion {
+    /// Returns the longest text that every candidate starts with.
+    /// ```
+    /// # use simpleterm::completion::Completion;
+    /// let completion: Completion = Completion { start: 0, candidates: vec![String::from("north"), String::from("northeast")] };
+    /// assert_eq!(completion.common_prefix(), "north");
+    /// assert_eq!(Completion::default().common_prefix(), "");
+    /// ```
+    pub fn common_prefix(&self) -> &str {
+        let first: &str = match self.candidates.first() {
+            Some(first) => first,
+            None => return "",
+        };
+
+        let mut end: usize = first.len();
+        for candidate in self.candidates.iter().skip(1) {
+            end = first.char_indices().zip(candidate.chars())
+                .find(|((_, a), b)| a != b)
+                .map_or(end.min(candidate.len()), |((i, _), _)| i.min(end));
+        }
+        &first[..end]
+    }
+}
+
+/// A completer suggests ways to finish what the user is typing when they press Tab.
+///
+/// Closures that take the input and the cursor position and return a Completion can be used as completers.
+pub trait Completer {
+    /// Returns the candidates for completing the given input, where the cursor is at the given byte index.
+    fn complete(&self, input: &str, cursor: usize) -> Completion;
+}
+
+impl<F: Fn(&str, usize) -> Completion> Completer for F {
+    fn complete(&self, input: &str, cursor: usize) -> Completion {
+        self(input, cursor)
+    }
+}
+
+/// Completes the word before the cursor with any of a list of words that start with it.
+///
+/// ```
+/// # use simpleterm::completion::*;
+/// let commands: WordCompleter = WordCompleter::new(&["look", "load", "quit"]);
+/// assert_eq!(
+///     commands.complete("save then lo", 12),
+///     Completion { start: 10, candidates: vec![String::from("look"), String::from("load")] },
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WordCompleter {
+    words: Vec<String>,
+}
+
+impl WordCompleter {
+    /// Creates a completer for the given words.
+    pub fn new<S: AsRef<str>>(words: &[S]) -> WordCompleter {
+        WordCompleter { words: words.iter().map(|word| String::from(word.as_ref())).collect() }
+    }
+}
+
+impl Completer for WordCompleter {
+    fn complete(&self, input: &str, cursor: usize) -> Completion {
+        let before: &str = &input[..cursor];
+        let start: usize = before.rfind(char::is_whitespace).map_or(0, |i| i + before[i..].chars().next().map_or(0, char::len_utf8));
+        let word: &str = &before[start..];
+
+        Completion {
+            start,
+            candidates: self.words.iter().filter(|candidate| candidate.starts_with(word)).cloned().collect(),
+        }
+    }
+}
@@ -1,9 +1,116 @@
-use piston_window::{*, types::{Color, FontSize}};
-use crate::{text::*, TEXT_OFFSET};
+use piston_window::{*, character::CharacterCache, types::{Color, FontSize}};
+use std::fmt::Debug;
+
+use crate::{text::*, Error};
+
+/// Everything a backend needs to know in order to draw a single frame of the terminal.
+pub struct Frame<'a> {
+    /// The background color of the terminal.
+    pub bg_color: Color,
+    /// The foreground color of the terminal.
+    pub fg_color: Color,
+    /// Whether or not to draw scanlines.
+    pub scanlines: bool,
+    /// The font size to draw the contents with.
+    pub font_size: FontSize,
+    /// The x and y offset of the text from the corners of the terminal.
+    pub text_offset: (f64, f64),
+    /// What to draw inside the terminal box.
+    pub contents: Contents<'a>,
+}
+
+/// The contents drawn inside the terminal box.
+pub enum Contents<'a> {
+    /// Ascii art, centered on the terminal.
+    Art(&'a [String]),
+    /// A message at the top of the terminal, with an optional input marker and input string at the bottom.
+    Text {
+        /// The lines of the current message.
+        message: &'a [String],
+        /// Whether or not to draw the input marker.
+        marker: bool,
+        /// The input string to draw, if any.
+        input: Option<&'a str>,
+        /// Where to draw the input cursor, as a byte index into the input string, if it should be drawn.
+        cursor: Option<usize>,
+        /// Where the message lines are within the whole message, if only part of it is shown.
+        scroll: Option<ScrollPosition>,
+        /// The part of the message lines the user has selected, if any.
+        selection: Option<Selection>,
+        /// The message line to draw with inverted colors, like the highlighted option of a menu, if any.
+        highlight: Option<usize>,
+        /// A short status like a countdown to draw at the right end of the input line, if any.
+        status: Option<&'a str>,
+    },
+}
+
+/// A range of message text, from a line and byte index in that line up to another line and byte index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Selection {
+    /// The line and byte index where the selection starts.
+    pub start: (usize, usize),
+    /// The line and byte index where the selection ends. This is never before the start.
+    pub end: (usize, usize),
+}
+
+/// Where the visible lines of a message are within the whole message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScrollPosition {
+    /// The index of the first visible line.
+    pub first: usize,
+    /// How many lines are visible.
+    pub visible: usize,
+    /// How many lines the whole message has.
+    pub total: usize,
+}
+
+/// Draws a complete frame of the terminal, from the background up to the foreground.
+/// ```
+/// # use simpleterm::{draw::*, testing::*, text::*, TEXT_OFFSET};
+/// let message: Vec<String> = vec![String::from("Hello, world!"), String::from("Second line")];
+/// let frame: Frame = Frame {
+///     bg_color: DARK_GREY,
+///     fg_color: GOLD,
+///     scanlines: true,
+///     font_size: 24,
+///     text_offset: TEXT_OFFSET,
+///     contents: Contents::Text { message: &message, marker: true, input: Some("typing[]"), cursor: None, scroll: None, selection: None, highlight: None, status: None },
+/// };
+/// assert_snapshot("draw_frame_text", &render_frame(&frame, (320, 240), "LeagueSpartan-Regular.ttf").unwrap());
+/// ```
+pub fn draw_frame<C, G>(frame: &Frame, win_size: Size, glyphs: &mut C, context: Context, graphics: &mut G) -> Result<(), Error>
+where
+    C: CharacterCache,
+    C::Error: Debug,
+    G: Graphics<Texture = C::Texture>,
+{
+    let bgc: Color = frame.bg_color;
+    let fgc: Color = frame.fg_color;
+    let font_size: FontSize = frame.font_size;
+    let offset: (f64, f64) = frame.text_offset;
+
+    clear(bgc, graphics);
+
+    draw_background(win_size, bgc, fgc, frame.scanlines, context, graphics);
+    match frame.contents {
+        Contents::Art(art) => draw_art(win_size, art, glyphs, font_size, fgc, context, graphics)?,
+        Contents::Text { message, marker, input, cursor, scroll, selection, highlight, status } => {
+            if let Some(selection) = selection { draw_selection(message, offset, selection, glyphs, font_size, fgc, context, graphics)?; }
+            draw_message(message, offset, glyphs, font_size, fgc, context, graphics)?;
+            if let Some(row) = highlight { draw_highlight(message, offset, row, glyphs, font_size, bgc, fgc, context, graphics)?; }
+            if let Some(scroll) = scroll { draw_scroll_indicator(win_size, offset, scroll, fgc, context, graphics); }
+            if marker { draw_input_marker(win_size, offset, glyphs, font_size, fgc, context, graphics)?; }
+            if let Some(input) = input { draw_input(win_size, offset, input, cursor, glyphs, font_size, fgc, context, graphics)?; }
+            if let Some(status) = status { draw_status(win_size, offset, status, glyphs, font_size, fgc, context, graphics)?; }
+        }
+    }
+    draw_foreground(win_size, bgc, frame.scanlines, context, graphics);
+    Ok(())
+}
 
 /// Displays a box around the text of the terminal, using the terminal's current colors and size.
 /// Also draws scanlines on the terminal background.
-pub fn draw_background(win_size: Size, bgc: Color, fgc: Color, lines: bool, context: Context, graphics: &mut G2d) {
+pub fn draw_background<G: Graphics>(win_size: Size, bgc: Color, fgc: Color, lines: bool, context: Context, graphics: &mut G) {
     rectangle(fgc, [10.0, 10.0, win_size.width - 20.0, win_size.height - 20.0], context.transform, graphics);
     rectangle(bgc, [15.0, 15.0, win_size.width - 30.0, win_size.height - 30.0], context.transform, graphics);
 
@@ -21,7 +128,25 @@ pub fn draw_background(win_size: Size, bgc: Color, fgc: Color, lines: bool, cont
 }
 
 /// Draws art centered on the terminal. If the art is bigger than the terminal can display, you'll only see the center portion of it.
-pub fn draw_art(win_size: Size, art: &[String], glyphs: &mut Glyphs, font_size: FontSize, fgc: Color, context: Context, graphics: &mut G2d) {
+/// ```
+/// # use simpleterm::{art::*, draw::*, testing::*, text::*, TEXT_OFFSET};
+/// let art: Vec<String> = MONA.split('\n').map(String::from).collect();
+/// let frame: Frame = Frame {
+///     bg_color: DARK_PURPLE,
+///     fg_color: OFF_WHITE,
+///     scanlines: false,
+///     font_size: 4,
+///     text_offset: TEXT_OFFSET,
+///     contents: Contents::Art(&art),
+/// };
+/// assert_snapshot("draw_art_mona", &render_frame(&frame, (320, 240), "LeagueMono-Regular.ttf").unwrap());
+/// ```
+pub fn draw_art<C, G>(win_size: Size, art: &[String], glyphs: &mut C, font_size: FontSize, fgc: Color, context: Context, graphics: &mut G) -> Result<(), Error>
+where
+    C: CharacterCache,
+    C::Error: Debug,
+    G: Graphics<Texture = C::Texture>,
+{
     let (x, y): (f64, f64) = place_art(win_size, art, font_size);
 
     let mut y_offset: f64 = 0.0;
@@ -32,16 +157,23 @@ pub fn draw_art(win_size: Size, art: &[String], glyphs: &mut Glyphs, font_size:
             &context.draw_state,
             context.transform.trans(x, y + y_offset),
             graphics,
-        ).unwrap();
+        ).map_err(render_error)?;
 
         y_offset += (font_size as f64) * 0.8;
     }
+    Ok(())
 }
 
 /// Draws text starting at the top of the terminal, using the terminal's current foreground color, font, and font size.
-pub fn draw_message(message: &[String], glyphs: &mut Glyphs, font_size: FontSize, fgc: Color, context: Context, graphics: &mut G2d)  {
-    let x = TEXT_OFFSET.0;
-    let y = TEXT_OFFSET.1;
+/// The offset is the distance of the text from the top left corner of the terminal.
+pub fn draw_message<C, G>(message: &[String], offset: (f64, f64), glyphs: &mut C, font_size: FontSize, fgc: Color, context: Context, graphics: &mut G) -> Result<(), Error>
+where
+    C: CharacterCache,
+    C::Error: Debug,
+    G: Graphics<Texture = C::Texture>,
+{
+    let x = offset.0;
+    let y = offset.1;
 
     let mut y_offset: f64 = 0.0;
     for line in message.iter() {
@@ -51,16 +183,96 @@ pub fn draw_message(message: &[String], glyphs: &mut Glyphs, font_size: FontSize
             &context.draw_state,
             context.transform.trans(x, y + y_offset),
             graphics,
-        ).unwrap();
+        ).map_err(render_error)?;
 
         y_offset += (font_size as f64) * 0.8;
     }
+    Ok(())
+}
+
+/// Highlights the selected part of the message, behind where draw_message draws the text.
+/// ```
+/// # use piston_window::clear;
+/// # use simpleterm::{draw::*, testing::*, text::*, TEXT_OFFSET};
+/// let message: Vec<String> = vec![String::from("first line"), String::from("second line")];
+/// let selection: Selection = Selection { start: (0, 6), end: (1, 6) };
+/// let buffer = render_with((320, 240), "LeagueSpartan-Regular.ttf", |_, glyphs, context, graphics| {
+///     clear(DARK_GREY, graphics);
+///     draw_selection(&message, TEXT_OFFSET, selection, glyphs, 24, GOLD, context, graphics)
+/// }).unwrap();
+/// assert_eq!(buffer.pixel(30, 45), [41, 41, 41, 255]);
+/// assert_ne!(buffer.pixel(30, 65), [41, 41, 41, 255]);
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn draw_selection<C, G>(message: &[String], offset: (f64, f64), selection: Selection, glyphs: &mut C, font_size: FontSize, fgc: Color, context: Context, graphics: &mut G) -> Result<(), Error>
+where
+    C: CharacterCache,
+    C::Error: Debug,
+    G: Graphics<Texture = C::Texture>,
+{
+    let line_height: f64 = (font_size as f64) * 0.8;
+    let color: Color = [fgc[0], fgc[1], fgc[2], 0.35];
+
+    for (i, line) in message.iter().enumerate().take(selection.end.0 + 1).skip(selection.start.0) {
+        let from: usize = if i == selection.start.0 { selection.start.1.min(line.len()) } else { 0 };
+        let to: usize = if i == selection.end.0 { selection.end.1.min(line.len()) } else { line.len() };
+        if from >= to { continue; }
+
+        let left: f64 = offset.0 + measure_text(glyphs, &line[..from], font_size)?;
+        let right: f64 = offset.0 + measure_text(glyphs, &line[..to], font_size)?;
+        let baseline: f64 = offset.1 + line_height * i as f64;
+        rectangle(color, [left, baseline - line_height * 0.9, right - left, line_height], context.transform, graphics);
+    }
+    Ok(())
+}
+
+/// Draws a line of the message with inverted colors, over where draw_message draws it.
+/// ```
+/// # use piston_window::clear;
+/// # use simpleterm::{draw::*, testing::*, text::*, TEXT_OFFSET};
+/// let message: Vec<String> = vec![String::from("1. north"), String::from("2. south")];
+/// let buffer = render_with((320, 240), "LeagueSpartan-Regular.ttf", |_, glyphs, context, graphics| {
+///     clear(DARK_GREY, graphics);
+///     draw_highlight(&message, TEXT_OFFSET, 1, glyphs, 24, DARK_GREY, GOLD, context, graphics)
+/// }).unwrap();
+/// assert_eq!(buffer.pixel(23, 60), [255, 166, 26, 255]);
+/// assert_eq!(buffer.pixel(23, 40), [41, 41, 41, 255]);
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn draw_highlight<C, G>(message: &[String], offset: (f64, f64), row: usize, glyphs: &mut C, font_size: FontSize, bgc: Color, fgc: Color, context: Context, graphics: &mut G) -> Result<(), Error>
+where
+    C: CharacterCache,
+    C::Error: Debug,
+    G: Graphics<Texture = C::Texture>,
+{
+    let line: &str = match message.get(row) {
+        Some(line) => line,
+        None => return Ok(()),
+    };
+
+    let line_height: f64 = (font_size as f64) * 0.8;
+    let baseline: f64 = offset.1 + line_height * row as f64;
+    let width: f64 = measure_text(glyphs, line, font_size)?;
+    rectangle(fgc, [offset.0 - 4.0, baseline - line_height * 0.9, width + 8.0, line_height], context.transform, graphics);
+
+    text::Text::new_color(bgc, font_size).draw(
+        line,
+        glyphs,
+        &context.draw_state,
+        context.transform.trans(offset.0, baseline),
+        graphics,
+    ).map_err(render_error)
 }
 
 /// Displays a marker before the input string at the bottom fo the terminal, using the terminal's current foreground color, font, and font size.
-pub fn draw_input_marker(win_size: Size, glyphs: &mut Glyphs, font_size: FontSize, fgc: Color, context: Context, graphics: &mut G2d) {
-    let x = TEXT_OFFSET.0;
-    let y = (win_size.height - TEXT_OFFSET.1) + 20.0;
+pub fn draw_input_marker<C, G>(win_size: Size, offset: (f64, f64), glyphs: &mut C, font_size: FontSize, fgc: Color, context: Context, graphics: &mut G) -> Result<(), Error>
+where
+    C: CharacterCache,
+    C::Error: Debug,
+    G: Graphics<Texture = C::Texture>,
+{
+    let x = offset.0;
+    let y = (win_size.height - offset.1) + 20.0;
 
     text::Text::new_color(fgc, font_size).draw(
         "> ",
@@ -68,13 +280,33 @@ pub fn draw_input_marker(win_size: Size, glyphs: &mut Glyphs, font_size: FontSiz
         &context.draw_state,
         context.transform.trans(x, y),
         graphics,
-    ).unwrap();
+    ).map_err(render_error)
 }
 
 /// Displays the current input string at the bottom of the terminal, using the terminal's current foreground color, font, and font size.
-pub fn draw_input(win_size: Size, message: &str, glyphs: &mut Glyphs, font_size: FontSize, fgc: Color, context: Context, graphics: &mut G2d)  {
-    let x = TEXT_OFFSET.0 + 20.0;
-    let y = (win_size.height - TEXT_OFFSET.1) + 20.0;
+/// If a cursor position is given, a cursor is drawn in front of the character at that byte index of the input.
+/// ```
+/// # use piston_window::clear;
+/// # use simpleterm::{draw::*, testing::*, text::*, TEXT_OFFSET};
+/// let cursor_x = |cursor: usize| {
+///     let buffer = render_with((320, 240), "LeagueMono-Regular.ttf", |win_size, glyphs, context, graphics| {
+///         clear(DARK_GREY, graphics);
+///         draw_input(win_size, TEXT_OFFSET, "...", Some(cursor), glyphs, 24, GOLD, context, graphics)
+///     }).unwrap();
+///     (0..320).find(|x| buffer.pixel(*x, 195) == [255, 166, 26, 255]).unwrap()
+/// };
+/// assert_eq!(cursor_x(0), 45);
+/// assert!(cursor_x(1) > cursor_x(0) && cursor_x(3) > cursor_x(1));
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn draw_input<C, G>(win_size: Size, offset: (f64, f64), message: &str, cursor: Option<usize>, glyphs: &mut C, font_size: FontSize, fgc: Color, context: Context, graphics: &mut G) -> Result<(), Error>
+where
+    C: CharacterCache,
+    C::Error: Debug,
+    G: Graphics<Texture = C::Texture>,
+{
+    let x = offset.0 + 20.0;
+    let y = (win_size.height - offset.1) + 20.0;
 
     text::Text::new_color(fgc, font_size).draw(
         message,
@@ -82,11 +314,75 @@ pub fn draw_input(win_size: Size, message: &str, glyphs: &mut Glyphs, font_size:
         &context.draw_state,
         context.transform.trans(x, y),
         graphics,
-    ).unwrap();
+    ).map_err(render_error)?;
+
+    if let Some(cursor) = cursor {
+        let size: f64 = font_size as f64;
+        let cursor_x: f64 = x + measure_text(glyphs, &message[..cursor.min(message.len())], font_size)?;
+        rectangle(fgc, [cursor_x, y - size * 0.7, (size * 0.1).max(2.0), size * 0.8], context.transform, graphics);
+    }
+    Ok(())
+}
+
+/// Displays a short status like a countdown at the right end of the input line, using the terminal's current foreground color, font, and font size.
+/// ```
+/// # use piston_window::clear;
+/// # use simpleterm::{draw::*, testing::*, text::*, TEXT_OFFSET};
+/// let buffer = render_with((320, 240), "LeagueMono-Regular.ttf", |win_size, glyphs, context, graphics| {
+///     clear(DARK_GREY, graphics);
+///     draw_status(win_size, TEXT_OFFSET, "||||", glyphs, 24, GOLD, context, graphics)
+/// }).unwrap();
+/// assert!((200..295).any(|x| buffer.pixel(x, 200) == [255, 166, 26, 255]));
+/// assert!((0..200).all(|x| buffer.pixel(x, 200) != [255, 166, 26, 255]));
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn draw_status<C, G>(win_size: Size, offset: (f64, f64), status: &str, glyphs: &mut C, font_size: FontSize, fgc: Color, context: Context, graphics: &mut G) -> Result<(), Error>
+where
+    C: CharacterCache,
+    C::Error: Debug,
+    G: Graphics<Texture = C::Texture>,
+{
+    let x = win_size.width - offset.0 - measure_text(glyphs, status, font_size)?;
+    let y = (win_size.height - offset.1) + 20.0;
+
+    text::Text::new_color(fgc, font_size).draw(
+        status,
+        glyphs,
+        &context.draw_state,
+        context.transform.trans(x, y),
+        graphics,
+    ).map_err(render_error)
+}
+
+/// Displays a scroll bar in the right margin of the terminal, showing which part of the message is visible.
+/// Nothing is drawn if the whole message is visible.
+/// ```
+/// # use piston_window::clear;
+/// # use simpleterm::{draw::*, testing::*, text::*, TEXT_OFFSET};
+/// let scroll: ScrollPosition = ScrollPosition { first: 0, visible: 5, total: 20 };
+/// let buffer = render_with((320, 240), "LeagueSpartan-Regular.ttf", |win_size, _, context, graphics| {
+///     clear(DARK_GREY, graphics);
+///     draw_scroll_indicator(win_size, TEXT_OFFSET, scroll, GOLD, context, graphics);
+///     Ok(())
+/// }).unwrap();
+/// assert_eq!(buffer.pixel(300, 30), [255, 166, 26, 255]);
+/// assert_ne!(buffer.pixel(300, 150), [255, 166, 26, 255]);
+/// ```
+pub fn draw_scroll_indicator<G: Graphics>(win_size: Size, offset: (f64, f64), scroll: ScrollPosition, fgc: Color, context: Context, graphics: &mut G) {
+    if scroll.total <= scroll.visible { return; }
+
+    let x: f64 = win_size.width - (offset.0 + 15.0) / 2.0 - 2.0;
+    let top: f64 = 20.0;
+    let height: f64 = (win_size.height - offset.1) - top;
+    let thumb_top: f64 = top + height * (scroll.first as f64 / scroll.total as f64);
+    let thumb_height: f64 = height * (scroll.visible as f64 / scroll.total as f64);
+
+    rectangle([fgc[0], fgc[1], fgc[2], 0.25], [x, top, 4.0, height], context.transform, graphics);
+    rectangle(fgc, [x, thumb_top, 4.0, thumb_height], context.transform, graphics);
 }
 
 /// Displays scanlines over the terminal text and a border around the terminal box, using the terminal's current size and background color.
-pub fn draw_foreground(win_size: Size, bgc: Color, lines: bool, context: Context, graphics: &mut G2d) {
+pub fn draw_foreground<G: Graphics>(win_size: Size, bgc: Color, lines: bool, context: Context, graphics: &mut G) {
     if lines {
         let line_color: Color = [bgc[0], bgc[1], bgc[2], 0.4];
         
@@ -100,3 +396,24 @@ pub fn draw_foreground(win_size: Size, bgc: Color, lines: bool, context: Context
     rectangle(bgc, [win_size.width - 10.0, 0.0, 10.0, win_size.height], context.transform, graphics);
     rectangle(bgc, [0.0, win_size.height - 10.0, win_size.width, 10.0], context.transform, graphics);
 }
+
+/// Returns the width in pixels of the given text, drawn with the given glyphs at the given font size.
+/// ```
+/// # use simpleterm::{draw::*, headless::*, font::*};
+/// let mut glyphs: HeadlessGlyphs = load_headless_font(&EmbeddedFont::LeagueSpartan.into()).unwrap();
+/// let narrow: f64 = measure_text(&mut glyphs, "iiii", 24).unwrap();
+/// let wide: f64 = measure_text(&mut glyphs, "WWWW", 24).unwrap();
+/// assert!(narrow < wide);
+/// ```
+pub fn measure_text<C>(glyphs: &mut C, text: &str, font_size: FontSize) -> Result<f64, Error>
+where
+    C: CharacterCache,
+    C::Error: Debug,
+{
+    glyphs.width(font_size, text).map_err(render_error)
+}
+
+// Wraps an error from the glyph cache as a render error.
+fn render_error<E: Debug>(error: E) -> Error {
+    Error::Render(format!("{:?}", error))
+}
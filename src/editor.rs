@@ -0,0 +1,192 @@
+use piston_window::{Key, keyboard::ModifierKey};
+use unicode_segmentation::UnicodeSegmentation;
+use crate::secret::{zero, zero_spare};
+
+/// A single line of input with a movable cursor, edited the way a shell prompt is.
+///
+/// The cursor is a byte index into the text, and always sits between two grapheme clusters.
+///
+/// ```
+/// # use piston_window::{Key, keyboard::ModifierKey};
+/// # use simpleterm::editor::LineEditor;
+/// let mut line: LineEditor = LineEditor::new();
+/// line.insert("hello world");
+/// line.handle_key(Key::Left, ModifierKey::CTRL);
+/// line.insert("big ");
+/// assert_eq!(line.text(), "hello big world");
+///
+/// line.handle_key(Key::W, ModifierKey::CTRL);
+/// assert_eq!(line.text(), "hello world");
+/// assert_eq!(line.cursor(), 6);
+///
+/// line.handle_key(Key::Home, ModifierKey::NO_MODIFIER);
+/// line.handle_key(Key::Right, ModifierKey::CTRL);
+/// line.handle_key(Key::Delete, ModifierKey::NO_MODIFIER);
+/// assert_eq!(line.text(), "helloworld");
+/// line.handle_key(Key::U, ModifierKey::CTRL);
+/// assert_eq!((line.text(), line.cursor()), ("world", 0));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LineEditor {
+    text: String,
+    cursor: usize,
+    capacity: Option<usize>,
+}
+
+impl LineEditor {
+    /// Creates an empty line.
+    pub fn new() -> LineEditor {
+        LineEditor::default()
+    }
+
+    /// Creates an empty line for secret text, which never holds more than the given number of bytes.
+    /// The text stays in the buffer reserved here instead of being moved as it grows, and removed text is overwritten with zeroes.
+    /// ```
+    /// # use simpleterm::editor::LineEditor;
+    /// let mut line: LineEditor = LineEditor::with_capacity(4);
+    /// line.insert("hunter2");
+    /// assert_eq!(line.text(), "hunt");
+    /// ```
+    pub fn with_capacity(capacity: usize) -> LineEditor {
+        LineEditor { text: String::with_capacity(capacity), cursor: 0, capacity: Some(capacity) }
+    }
+
+    /// Returns the text of the line.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the position of the cursor, as a byte index into the text.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Replaces the text of the line and moves the cursor to the end.
+    pub fn set_text(&mut self, text: &str) {
+        self.clear();
+        self.text.push_str(self.fitting(text, 0));
+        self.cursor = self.text.len();
+    }
+
+    /// Removes all of the text.
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+        self.wipe();
+    }
+
+    /// Removes all of the text and returns it.
+    pub fn take(&mut self) -> String {
+        self.cursor = 0;
+        std::mem::take(&mut self.text)
+    }
+
+    /// Inserts text at the cursor and moves the cursor past it. Control characters, and any text that doesn't fit in the line's capacity, are left out.
+    /// ```
+    /// # use simpleterm::editor::LineEditor;
+    /// let mut line: LineEditor = LineEditor::new();
+    /// line.insert("a\u{17}b");
+    /// assert_eq!(line.text(), "ab");
+    /// ```
+    pub fn insert(&mut self, text: &str) {
+        let mut text: String = text.chars().filter(|c| !c.is_control()).collect();
+        let fitting: &str = self.fitting(&text, 0);
+        self.text.insert_str(self.cursor, fitting);
+        self.cursor += fitting.len();
+        if self.capacity.is_some() { zero(&mut text); }
+    }
+
+    /// Replaces the text between the given byte index and the cursor, and moves the cursor past the new text.
+    /// ```
+    /// # use simpleterm::editor::LineEditor;
+    /// let mut line: LineEditor = LineEditor::new();
+    /// line.insert("go no");
+    /// line.replace_before_cursor(3, "north");
+    /// assert_eq!((line.text(), line.cursor()), ("go north", 8));
+    /// ```
+    pub fn replace_before_cursor(&mut self, start: usize, text: &str) {
+        let start: usize = start.min(self.cursor);
+        let text: &str = self.fitting(text, self.cursor - start);
+        self.text.replace_range(start..self.cursor, text);
+        self.cursor = start + text.len();
+        self.wipe();
+    }
+
+    /// Applies the editing command for the given key, with the given modifier keys held down.
+    /// Returns false if the key isn't an editing command.
+    ///
+    /// | Key | Command |
+    /// | --- | --- |
+    /// | Left, Right | Move the cursor by one character |
+    /// | Ctrl+Left, Ctrl+Right | Move the cursor by one word |
+    /// | Home, End | Move the cursor to the start or end of the line |
+    /// | Backspace, Delete | Remove the character before or after the cursor |
+    /// | Ctrl+W | Remove the word before the cursor |
+    /// | Ctrl+U | Remove everything before the cursor |
+    pub fn handle_key(&mut self, key: Key, modifiers: ModifierKey) -> bool {
+        let ctrl: bool = modifiers.contains(ModifierKey::CTRL);
+
+        match key {
+            Key::Left if ctrl => self.cursor = self.word_start(),
+            Key::Right if ctrl => self.cursor = self.word_end(),
+            Key::Left => self.cursor = self.previous_boundary(),
+            Key::Right => self.cursor = self.next_boundary(),
+            Key::Home => self.cursor = 0,
+            Key::End => self.cursor = self.text.len(),
+            Key::Backspace => self.remove(self.previous_boundary(), self.cursor),
+            Key::Delete => self.remove(self.cursor, self.next_boundary()),
+            Key::W if ctrl => self.remove(self.word_start(), self.cursor),
+            Key::U if ctrl => self.remove(0, self.cursor),
+            _ => return false,
+        }
+        true
+    }
+
+    // Removes the text between the given byte indices and leaves the cursor where the text was.
+    fn remove(&mut self, start: usize, end: usize) {
+        self.text.replace_range(start..end, "");
+        self.cursor = start;
+        self.wipe();
+    }
+
+    // Returns as much of the start of the given text as fits in the line's capacity, once the given number of bytes are removed from the line.
+    fn fitting<'t>(&self, text: &'t str, removed: usize) -> &'t str {
+        match self.capacity {
+            Some(capacity) => {
+                let room: usize = capacity.saturating_sub(self.text.len() - removed);
+                let end: usize = text.grapheme_indices(true).map(|(i, g)| i + g.len()).take_while(|end| *end <= room).last().unwrap_or(0);
+                &text[..end]
+            }
+            None => text,
+        }
+    }
+
+    // Overwrites the text removed from a line with a fixed capacity, which is left past the end of the text.
+    fn wipe(&mut self) {
+        if self.capacity.is_some() { zero_spare(&mut self.text); }
+    }
+
+    // Returns the start of the grapheme before the cursor.
+    fn previous_boundary(&self) -> usize {
+        self.text[..self.cursor].grapheme_indices(true).next_back().map_or(0, |(i, _)| i)
+    }
+
+    // Returns the end of the grapheme after the cursor.
+    fn next_boundary(&self) -> usize {
+        self.text[self.cursor..].graphemes(true).next().map_or(self.cursor, |g| self.cursor + g.len())
+    }
+
+    // Returns the start of the word before the cursor, skipping any whitespace right before it.
+    fn word_start(&self) -> usize {
+        let before: &str = self.text[..self.cursor].trim_end();
+        before.rfind(char::is_whitespace).map_or(0, |i| i + before[i..].chars().next().map_or(0, char::len_utf8))
+    }
+
+    // Returns the end of the word after the cursor, skipping any whitespace right after it.
+    fn word_end(&self) -> usize {
+        let after: &str = &self.text[self.cursor..];
+        let skipped: usize = after.len() - after.trim_start().len();
+        let word: &str = &after[skipped..];
+        self.cursor + skipped + word.find(char::is_whitespace).unwrap_or(word.len())
+    }
+}
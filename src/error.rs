@@ -0,0 +1,36 @@
+use std::{fmt, path::PathBuf};
+
+/// Everything that can go wrong while creating or drawing a terminal.
+#[derive(Debug)]
+pub enum Error {
+    /// The font file at the given path could not be read.
+    FontNotFound(PathBuf),
+    /// The given font could not be parsed.
+    FontParse(String),
+    /// The art file at the given path could not be read.
+    ArtNotFound(PathBuf),
+    /// The history file at the given path could not be read or written.
+    HistoryFile(PathBuf),
+    /// The terminal's settings can't be used, for the given reason.
+    InvalidConfig(String),
+    /// The window could not be created, for the given reason.
+    WindowCreation(String),
+    /// A frame could not be drawn, for the given reason.
+    Render(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::FontNotFound(path) => write!(f, "font not found: {}", path.display()),
+            Error::FontParse(font) => write!(f, "could not parse font: {}", font),
+            Error::ArtNotFound(path) => write!(f, "art not found: {}", path.display()),
+            Error::HistoryFile(path) => write!(f, "could not read or write history file: {}", path.display()),
+            Error::InvalidConfig(reason) => write!(f, "invalid terminal settings: {}", reason),
+            Error::WindowCreation(reason) => write!(f, "could not create window: {}", reason),
+            Error::Render(reason) => write!(f, "could not render frame: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
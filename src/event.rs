@@ -0,0 +1,11 @@
+/// Something a terminal finished while it was being driven frame by frame with
+/// [update](../terminal/struct.Terminal.html#method.update), returned by [poll](../terminal/struct.Terminal.html#method.poll).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TerminalEvent {
+    /// A message queued with push_show was typed out and shown for its whole time.
+    MessageShown,
+    /// The user submitted input to a prompt queued with push_ask.
+    InputSubmitted(String),
+    /// The window was closed, so the terminal stopped and dropped any queued messages and prompts.
+    Closed,
+}
@@ -0,0 +1,154 @@
+use rusttype::Font;
+use std::{fmt, fs, path::{Path, PathBuf}, sync::Arc};
+
+use crate::Error;
+
+/// The fonts that come with simpleterm.
+///
+/// With the default "embedded-fonts" feature these are compiled into the crate,
+/// otherwise they are read from the resources folder like any other font file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmbeddedFont {
+    /// LeagueMono-Regular.ttf, a monospace font. This is the default art font.
+    LeagueMono,
+    /// LeagueSpartan-Regular.ttf, a proportional font.
+    LeagueSpartan,
+}
+
+impl EmbeddedFont {
+    /// Returns the file name of this font in the resources folder.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            EmbeddedFont::LeagueMono => "LeagueMono-Regular.ttf",
+            EmbeddedFont::LeagueSpartan => "LeagueSpartan-Regular.ttf",
+        }
+    }
+
+    /// Returns the bundled font with the given file name, if there is one.
+    /// ```
+    /// # use simpleterm::font::*;
+    /// assert_eq!(EmbeddedFont::from_file_name("LeagueMono-Regular.ttf"), Some(EmbeddedFont::LeagueMono));
+    /// assert_eq!(EmbeddedFont::from_file_name("Comic-Sans.ttf"), None);
+    /// ```
+    pub fn from_file_name(name: &str) -> Option<EmbeddedFont> {
+        [EmbeddedFont::LeagueMono, EmbeddedFont::LeagueSpartan].iter().copied().find(|font| font.file_name() == name)
+    }
+
+    /// Returns the contents of the font file compiled into the crate.
+    /// These are used even when the program isn't run from the crate's folder.
+    /// ```
+    /// # use std::env;
+    /// # use simpleterm::{font::*, headless::HeadlessBackend, text::*, terminal::Terminal};
+    /// env::set_current_dir(env::temp_dir()).unwrap();
+    /// let term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// assert!(!EmbeddedFont::LeagueMono.bytes().is_empty());
+    /// ```
+    #[cfg(feature = "embedded-fonts")]
+    pub fn bytes(self) -> &'static [u8] {
+        match self {
+            EmbeddedFont::LeagueMono => include_bytes!("../resources/LeagueMono-Regular.ttf"),
+            EmbeddedFont::LeagueSpartan => include_bytes!("../resources/LeagueSpartan-Regular.ttf"),
+        }
+    }
+}
+
+/// Where a font is loaded from.
+///
+/// Anything that converts into a FontSource can be given to the terminal's font functions.
+/// A file name of one of the bundled fonts becomes an Embedded font, and any other string becomes a Path.
+/// ```
+/// # use simpleterm::font::*;
+/// assert_eq!(FontSource::from("LeagueSpartan-Regular.ttf"), FontSource::Embedded(EmbeddedFont::LeagueSpartan));
+/// assert_eq!(FontSource::from("Custom.ttf"), FontSource::Path("Custom.ttf".into()));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum FontSource {
+    /// One of the fonts that come with simpleterm.
+    Embedded(EmbeddedFont),
+    /// A font file. The terminal looks up relative paths in its resource folders.
+    Path(PathBuf),
+    /// The contents of a font file that is already in memory.
+    Bytes(Arc<[u8]>),
+}
+
+impl From<EmbeddedFont> for FontSource {
+    fn from(font: EmbeddedFont) -> FontSource {
+        FontSource::Embedded(font)
+    }
+}
+
+impl From<&str> for FontSource {
+    fn from(name: &str) -> FontSource {
+        match EmbeddedFont::from_file_name(name) {
+            Some(font) => FontSource::Embedded(font),
+            None => FontSource::Path(PathBuf::from(name)),
+        }
+    }
+}
+
+impl From<String> for FontSource {
+    fn from(name: String) -> FontSource {
+        FontSource::from(name.as_str())
+    }
+}
+
+impl From<PathBuf> for FontSource {
+    fn from(path: PathBuf) -> FontSource {
+        FontSource::Path(path)
+    }
+}
+
+impl From<&Path> for FontSource {
+    fn from(path: &Path) -> FontSource {
+        FontSource::Path(path.to_path_buf())
+    }
+}
+
+impl From<Vec<u8>> for FontSource {
+    fn from(bytes: Vec<u8>) -> FontSource {
+        FontSource::Bytes(bytes.into())
+    }
+}
+
+impl From<&'static [u8]> for FontSource {
+    fn from(bytes: &'static [u8]) -> FontSource {
+        FontSource::Bytes(bytes.into())
+    }
+}
+
+impl fmt::Display for FontSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FontSource::Embedded(font) => write!(f, "{}", font.file_name()),
+            FontSource::Path(path) => write!(f, "{}", path.display()),
+            FontSource::Bytes(bytes) => write!(f, "font in memory ({} bytes)", bytes.len()),
+        }
+    }
+}
+
+/// Reads and parses the font from the given source.
+/// Paths are read as they are, so use [ResourcePath::resolve_font](../resources/struct.ResourcePath.html#method.resolve_font) first to search the resource folders.
+/// ```
+/// # use simpleterm::{font::*, Error};
+/// assert!(read_font(&EmbeddedFont::LeagueMono.into()).is_ok());
+/// assert!(matches!(read_font(&"Missing.ttf".into()), Err(Error::FontNotFound(_))));
+/// assert!(matches!(read_font(&"resources/splash.png".into()), Err(Error::FontParse(_))));
+/// assert!(matches!(read_font(&vec![0, 1, 2, 3].into()), Err(Error::FontParse(_))));
+/// ```
+pub fn read_font(source: &FontSource) -> Result<Font<'static>, Error> {
+    let font = match source {
+        #[cfg(feature = "embedded-fonts")]
+        FontSource::Embedded(font) => Font::from_bytes(font.bytes()),
+        #[cfg(not(feature = "embedded-fonts"))]
+        FontSource::Embedded(_) => return read_font(&crate::resources::ResourcePath::default().resolve_font(source)?),
+        FontSource::Path(path) => Font::from_bytes(read_file(path)?),
+        FontSource::Bytes(bytes) => Font::from_bytes(bytes.clone()),
+    };
+
+    font.map_err(|_| Error::FontParse(source.to_string()))
+}
+
+// Reads the contents of a font file.
+fn read_file(path: &Path) -> Result<Vec<u8>, Error> {
+    fs::read(path).map_err(|_| Error::FontNotFound(path.to_path_buf()))
+}
@@ -0,0 +1,363 @@
+use piston_window::{*, glyph_cache::rusttype::GlyphCache, texture::*, types::{Color, FontSize}};
+use std::{collections::VecDeque, thread, time::{Duration, Instant}};
+
+use crate::{backend::Backend, draw::*, font::*, Error};
+
+/// The glyph cache used by the headless backend.
+pub type HeadlessGlyphs = GlyphCache<'static, (), BufferTexture>;
+
+/// How long the headless backend waits between render events, to mimic a 60 fps window.
+pub const FRAME_TIME: Duration = Duration::from_millis(16);
+
+/// A backend that renders the terminal into an in-memory RGBA buffer instead of a window.
+///
+/// It produces a render event every FRAME_TIME until it is closed, so the terminal's timers work as usual.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use simpleterm::{headless::HeadlessBackend, text::*, terminal::Terminal};
+/// let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+/// term.show("Hello!", Duration::from_millis(100));
+/// assert!(term.backend.frame_count() > 0);
+/// assert_eq!(term.backend.buffer().pixel(5, 5), [41, 41, 41, 255]);
+/// ```
+pub struct HeadlessBackend {
+    buffer: FrameBuffer,
+    glyphs: Option<HeadlessGlyphs>,
+    last_frame: Option<Instant>,
+    frames: u64,
+    inputs: VecDeque<Input>,
+    closed: bool,
+}
+
+impl HeadlessBackend {
+    /// Creates a new headless backend with a buffer of the given (width, height) size.
+    pub fn new(size: (u32, u32)) -> HeadlessBackend {
+        HeadlessBackend {
+            buffer: FrameBuffer::new(size.0, size.1),
+            glyphs: None,
+            last_frame: None,
+            frames: 0,
+            inputs: VecDeque::new(),
+            closed: false,
+        }
+    }
+
+    /// Returns the buffer containing the most recently rendered frame.
+    pub fn buffer(&self) -> &FrameBuffer {
+        &self.buffer
+    }
+
+    /// Returns the number of frames rendered so far.
+    pub fn frame_count(&self) -> u64 {
+        self.frames
+    }
+
+    /// Adds an input to be given out before the next render event, as if it came from the window's keyboard or mouse.
+    /// 
+    /// Unlike the input from an [InputSource](../input/trait.InputSource.html), this also reaches the terminal while it is typing out a message.
+    /// 
+    /// ```
+    /// # use std::time::{Duration, Instant};
+    /// # use piston_window::{Button, ButtonArgs, ButtonState, Input, Key};
+    /// # use simpleterm::{headless::HeadlessBackend, text::*, terminal::Terminal};
+    /// let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// term.type_time = Duration::from_secs(1);
+    /// term.skip_key = Some(Key::Space);
+    /// for key in [Key::X, Key::Space].iter() {
+    ///     term.backend.queue_input(Input::Button(ButtonArgs { state: ButtonState::Press, button: Button::Keyboard(*key), scancode: None }));
+    /// }
+    ///
+    /// let start: Instant = Instant::now();
+    /// term.show("This would take a long time to type.", Duration::from_millis(0));
+    /// assert!(start.elapsed() < Duration::from_secs(5));
+    /// ```
+    pub fn queue_input(&mut self, input: Input) {
+        self.inputs.push_back(input);
+    }
+
+    /// Stops producing events, as if the window had been closed.
+    pub fn close(&mut self) {
+        self.closed = true;
+    }
+}
+
+impl Backend for HeadlessBackend {
+    fn next_event(&mut self) -> Option<Event> {
+        if self.closed { return None; }
+        if let Some(input) = self.inputs.pop_front() { return Some(Event::Input(input, None)); }
+
+        if let Some(last) = self.last_frame {
+            let elapsed: Duration = last.elapsed();
+            if elapsed < FRAME_TIME { thread::sleep(FRAME_TIME - elapsed); }
+        }
+        self.last_frame = Some(Instant::now());
+
+        let size: [u32; 2] = [self.buffer.width, self.buffer.height];
+        Some(Event::Loop(Loop::Render(RenderArgs {
+            ext_dt: 0.0,
+            window_size: [size[0] as f64, size[1] as f64],
+            draw_size: size,
+        })))
+    }
+
+    fn size(&self) -> Size {
+        (self.buffer.width, self.buffer.height).into()
+    }
+
+    fn load_font(&mut self, font: &FontSource) -> Result<(), Error> {
+        self.glyphs = Some(load_headless_font(font)?);
+        Ok(())
+    }
+
+    fn text_width(&mut self, text: &str, font_size: FontSize) -> Result<f64, Error> {
+        match self.glyphs.as_mut() {
+            Some(glyphs) => measure_text(glyphs, text, font_size),
+            None => Err(Error::Render(String::from("no font is loaded"))),
+        }
+    }
+
+    fn render(&mut self, event: &Event, frame: &Frame) -> Result<(), Error> {
+        let win_size: Size = self.size();
+
+        if let (Some(args), Some(glyphs)) = (event.render_args(), self.glyphs.as_mut()) {
+            let context: Context = Context::new_viewport(args.viewport());
+            draw_frame(frame, win_size, glyphs, context, &mut self.buffer)?;
+            self.frames += 1;
+        }
+        Ok(())
+    }
+
+    fn resize(&mut self, new_size: Size) -> Result<(), Error> {
+        self.buffer = FrameBuffer::new(new_size.width as u32, new_size.height as u32);
+        Ok(())
+    }
+}
+
+/// Returns the glyph cache generated from the given font, for drawing into a FrameBuffer.
+pub fn load_headless_font(font: &FontSource) -> Result<HeadlessGlyphs, Error> {
+    Ok(GlyphCache::from_font(read_font(font)?, (), TextureSettings::new()))
+}
+
+/// An RGBA texture stored in memory, used for the glyphs drawn by the headless backend.
+pub struct BufferTexture {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl BufferTexture {
+    // Returns the color of the texel at the given texture coordinates, with each channel between 0 and 1.
+    fn sample(&self, uv: [f32; 2]) -> [f32; 4] {
+        let x: u32 = ((uv[0] * self.width as f32) as u32).min(self.width - 1);
+        let y: u32 = ((uv[1] * self.height as f32) as u32).min(self.height - 1);
+        let i: usize = ((y * self.width + x) * 4) as usize;
+
+        let p: &[u8] = &self.pixels[i..i + 4];
+        [p[0] as f32 / 255.0, p[1] as f32 / 255.0, p[2] as f32 / 255.0, p[3] as f32 / 255.0]
+    }
+}
+
+impl ImageSize for BufferTexture {
+    fn get_size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
+
+impl TextureOp<()> for BufferTexture {
+    type Error = String;
+}
+
+impl CreateTexture<()> for BufferTexture {
+    fn create<S: Into<[u32; 2]>>(_: &mut (), _: Format, memory: &[u8], size: S, _: &TextureSettings) -> Result<Self, Self::Error> {
+        let [width, height] = size.into();
+        if memory.len() != (width * height * 4) as usize {
+            return Err(format!("Expected {} bytes for a {}x{} texture, got {}", width * height * 4, width, height, memory.len()));
+        }
+
+        Ok(BufferTexture { width, height, pixels: memory.to_vec() })
+    }
+}
+
+impl UpdateTexture<()> for BufferTexture {
+    fn update<O, S>(&mut self, _: &mut (), _: Format, memory: &[u8], offset: O, size: S) -> Result<(), Self::Error>
+    where
+        O: Into<[u32; 2]>,
+        S: Into<[u32; 2]>,
+    {
+        let [x, y] = offset.into();
+        let [width, height] = size.into();
+        if x + width > self.width || y + height > self.height || memory.len() != (width * height * 4) as usize {
+            return Err(format!("Update of {}x{} at ({}, {}) does not fit in a {}x{} texture", width, height, x, y, self.width, self.height));
+        }
+
+        for row in 0..height {
+            let src: usize = (row * width * 4) as usize;
+            let dst: usize = (((y + row) * self.width + x) * 4) as usize;
+            self.pixels[dst..dst + (width * 4) as usize].copy_from_slice(&memory[src..src + (width * 4) as usize]);
+        }
+        Ok(())
+    }
+}
+
+/// An in-memory RGBA image that piston's 2d graphics can be drawn into.
+///
+/// Triangles are filled by testing pixel centers, and colors are alpha blended in the order they are drawn.
+/// The draw state is ignored, since the terminal never clips.
+///
+/// ```
+/// # use piston_window::{clear, rectangle, Context};
+/// # use simpleterm::{headless::FrameBuffer, text::*};
+/// let mut buffer: FrameBuffer = FrameBuffer::new(4, 4);
+/// let context: Context = Context::new_abs(4.0, 4.0);
+/// clear(DARK_GREY, &mut buffer);
+/// rectangle(OFF_WHITE, [0.0, 0.0, 2.0, 2.0], context.transform, &mut buffer);
+/// assert_eq!(buffer.pixel(1, 1), [250, 245, 240, 255]);
+/// assert_eq!(buffer.pixel(2, 2), [41, 41, 41, 255]);
+/// ```
+pub struct FrameBuffer {
+    /// The width of the buffer in pixels.
+    pub width: u32,
+    /// The height of the buffer in pixels.
+    pub height: u32,
+    pixels: Vec<u8>,
+}
+
+impl FrameBuffer {
+    /// Creates a new transparent buffer with the given width and height.
+    pub fn new(width: u32, height: u32) -> FrameBuffer {
+        FrameBuffer {
+            width,
+            height,
+            pixels: vec![0; (width * height * 4) as usize],
+        }
+    }
+
+    /// Returns the raw RGBA bytes of the buffer, row by row from the top left corner.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Returns the RGBA value of the pixel at the given position.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i: usize = ((y * self.width + x) * 4) as usize;
+        [self.pixels[i], self.pixels[i + 1], self.pixels[i + 2], self.pixels[i + 3]]
+    }
+
+    // Blends the given color over the pixel at the given position.
+    fn blend(&mut self, x: u32, y: u32, color: [f32; 4]) {
+        let i: usize = ((y * self.width + x) * 4) as usize;
+        let alpha: f32 = color[3].clamp(0.0, 1.0);
+
+        for (channel, src) in color.iter().take(3).enumerate() {
+            let dst: f32 = self.pixels[i + channel] as f32 / 255.0;
+            self.pixels[i + channel] = to_byte(src.clamp(0.0, 1.0) * alpha + dst * (1.0 - alpha));
+        }
+        let dst_alpha: f32 = self.pixels[i + 3] as f32 / 255.0;
+        self.pixels[i + 3] = to_byte(alpha + dst_alpha * (1.0 - alpha));
+    }
+
+    // Converts a vertex from normalized device coordinates to pixel coordinates.
+    fn to_pixels(&self, vertex: [f32; 2]) -> [f32; 2] {
+        [
+            (vertex[0] + 1.0) * 0.5 * self.width as f32,
+            (1.0 - vertex[1]) * 0.5 * self.height as f32,
+        ]
+    }
+
+    // Fills a triangle, asking the shader for the color at each covered pixel given its barycentric weights.
+    fn fill_triangle<S: FnMut([f32; 3]) -> [f32; 4]>(&mut self, triangle: [[f32; 2]; 3], mut shader: S) {
+        let mut order: [usize; 3] = [0, 1, 2];
+        let mut v: [[f32; 2]; 3] = [self.to_pixels(triangle[0]), self.to_pixels(triangle[1]), self.to_pixels(triangle[2])];
+
+        let mut area: f32 = edge(v[0], v[1], v[2]);
+        if area == 0.0 { return; }
+        if area < 0.0 {
+            v.swap(1, 2);
+            order.swap(1, 2);
+            area = -area;
+        }
+
+        let min_x: u32 = v.iter().map(|p| p[0]).fold(f32::MAX, f32::min).max(0.0) as u32;
+        let min_y: u32 = v.iter().map(|p| p[1]).fold(f32::MAX, f32::min).max(0.0) as u32;
+        let max_x: u32 = (v.iter().map(|p| p[0]).fold(f32::MIN, f32::max).ceil().max(0.0) as u32).min(self.width);
+        let max_y: u32 = (v.iter().map(|p| p[1]).fold(f32::MIN, f32::max).ceil().max(0.0) as u32).min(self.height);
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let p: [f32; 2] = [x as f32 + 0.5, y as f32 + 0.5];
+                let w: [f32; 3] = [edge(v[1], v[2], p), edge(v[2], v[0], p), edge(v[0], v[1], p)];
+
+                let inside: bool = covers(w[0], v[1], v[2]) && covers(w[1], v[2], v[0]) && covers(w[2], v[0], v[1]);
+                if inside {
+                    let mut weights: [f32; 3] = [0.0; 3];
+                    for (i, &o) in order.iter().enumerate() { weights[o] = w[i] / area; }
+                    let color: [f32; 4] = shader(weights);
+                    self.blend(x, y, color);
+                }
+            }
+        }
+    }
+}
+
+impl Graphics for FrameBuffer {
+    type Texture = BufferTexture;
+
+    fn clear_color(&mut self, color: Color) {
+        let bytes: [u8; 4] = [to_byte(color[0]), to_byte(color[1]), to_byte(color[2]), to_byte(color[3])];
+        for pixel in self.pixels.chunks_mut(4) {
+            pixel.copy_from_slice(&bytes);
+        }
+    }
+
+    fn clear_stencil(&mut self, _value: u8) {}
+
+    fn tri_list<F>(&mut self, _draw_state: &DrawState, color: &[f32; 4], mut f: F)
+    where
+        F: FnMut(&mut dyn FnMut(&[[f32; 2]])),
+    {
+        let color: [f32; 4] = *color;
+        f(&mut |vertices: &[[f32; 2]]| {
+            for triangle in vertices.chunks_exact(3) {
+                self.fill_triangle([triangle[0], triangle[1], triangle[2]], |_| color);
+            }
+        });
+    }
+
+    fn tri_list_uv<F>(&mut self, _draw_state: &DrawState, color: &[f32; 4], texture: &BufferTexture, mut f: F)
+    where
+        F: FnMut(&mut dyn FnMut(&[[f32; 2]], &[[f32; 2]])),
+    {
+        let color: [f32; 4] = *color;
+        f(&mut |vertices: &[[f32; 2]], uvs: &[[f32; 2]]| {
+            for (triangle, uv) in vertices.chunks_exact(3).zip(uvs.chunks_exact(3)) {
+                self.fill_triangle([triangle[0], triangle[1], triangle[2]], |w| {
+                    let u: f32 = uv[0][0] * w[0] + uv[1][0] * w[1] + uv[2][0] * w[2];
+                    let v: f32 = uv[0][1] * w[0] + uv[1][1] * w[1] + uv[2][1] * w[2];
+                    let texel: [f32; 4] = texture.sample([u, v]);
+                    [color[0] * texel[0], color[1] * texel[1], color[2] * texel[2], color[3] * texel[3]]
+                });
+            }
+        });
+    }
+}
+
+// Returns twice the signed area of the triangle (a, b, p). Positive when p is on the inner side of the edge from a to b.
+fn edge(a: [f32; 2], b: [f32; 2], p: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+}
+
+// Decides if a pixel is covered by an edge. Pixels exactly on an edge only belong to one of the two triangles sharing it.
+fn covers(weight: f32, a: [f32; 2], b: [f32; 2]) -> bool {
+    if weight == 0.0 {
+        let (dx, dy): (f32, f32) = (b[0] - a[0], b[1] - a[1]);
+        dy > 0.0 || (dy == 0.0 && dx < 0.0)
+    } else {
+        weight > 0.0
+    }
+}
+
+// Converts a color channel between 0 and 1 to a byte.
+fn to_byte(channel: f32) -> u8 {
+    (channel.clamp(0.0, 1.0) * 255.0).round() as u8
+}
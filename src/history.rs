@@ -0,0 +1,259 @@
+use piston_window::{Key, keyboard::ModifierKey};
+use std::{fs, path::{Path, PathBuf}};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{editor::LineEditor, Error};
+
+/// How many inputs a history remembers by default.
+pub const HISTORY_LIMIT: usize = 500;
+
+/// The inputs a user has submitted, oldest first.
+///
+/// A terminal keeps one history for all of its prompts, but a separate history can be given to
+/// [ask_with_history](../terminal/struct.Terminal.html#method.ask_with_history) for each kind of prompt.
+///
+/// ```
+/// # use simpleterm::history::History;
+/// let mut history: History = History::new();
+/// history.push("look");
+/// history.push("look");
+/// history.push("go north");
+/// history.push("  ");
+/// assert_eq!(history.entries(), &[String::from("look"), String::from("go north")]);
+/// assert_eq!(history.search("o", 2), Some(1));
+/// assert_eq!(history.search("oo", 2), Some(0));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct History {
+    entries: Vec<String>,
+    /// How many inputs to remember. The oldest inputs are forgotten first.
+    pub limit: usize,
+    path: Option<PathBuf>,
+}
+
+impl History {
+    /// Creates an empty history that isn't saved anywhere.
+    pub fn new() -> History {
+        History { entries: Vec::new(), limit: HISTORY_LIMIT, path: None }
+    }
+
+    /// Reads a history from the given file, with one input per line.
+    /// If the file doesn't exist yet the history starts out empty. [save](#method.save) writes the history back to the same file.
+    ///
+    /// ```
+    /// # use std::{env, fs, path::PathBuf};
+    /// # use simpleterm::history::History;
+    /// let path: PathBuf = env::temp_dir().join("simpleterm-history-doc.txt");
+    /// # let _ = fs::remove_file(&path);
+    /// let mut history: History = History::open(&path).unwrap();
+    /// history.push("inventory");
+    /// history.save().unwrap();
+    ///
+    /// assert_eq!(History::open(&path).unwrap().entries(), &[String::from("inventory")]);
+    /// ```
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<History, Error> {
+        let path: &Path = path.as_ref();
+        let mut history: History = History::new();
+
+        if path.exists() {
+            let contents: String = fs::read_to_string(path).map_err(|_| Error::HistoryFile(path.to_path_buf()))?;
+            for line in contents.lines() {
+                history.push(line);
+            }
+        }
+
+        history.path = Some(path.to_path_buf());
+        Ok(history)
+    }
+
+    /// Writes the history to the file it was opened from. Does nothing if it wasn't opened from a file.
+    pub fn save(&self) -> Result<(), Error> {
+        match &self.path {
+            Some(path) => fs::write(path, self.entries.iter().map(|entry| format!("{}\n", entry)).collect::<String>())
+                .map_err(|_| Error::HistoryFile(path.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the remembered inputs, oldest first.
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Remembers an input. Blank inputs and repeats of the last input are left out.
+    pub fn push(&mut self, entry: &str) {
+        if entry.trim().is_empty() || self.entries.last().map(String::as_str) == Some(entry) { return; }
+
+        self.entries.push(String::from(entry));
+        let extra: usize = self.entries.len().saturating_sub(self.limit);
+        self.entries.drain(..extra);
+    }
+
+    /// Returns the index of the newest input before the given index that contains the query.
+    pub fn search(&self, query: &str, before: usize) -> Option<usize> {
+        self.entries[..before.min(self.entries.len())].iter().rposition(|entry| entry.contains(query))
+    }
+}
+
+impl Default for History {
+    fn default() -> History {
+        History::new()
+    }
+}
+
+/// Tracks a user moving through a history with Up and Down, or searching it with Ctrl+R, while editing a line.
+///
+/// ```
+/// # use piston_window::{Key, keyboard::ModifierKey};
+/// # use simpleterm::{editor::LineEditor, history::*};
+/// let mut history: History = History::new();
+/// history.push("take lamp");
+/// history.push("go north");
+///
+/// let mut line: LineEditor = LineEditor::new();
+/// let mut recall: Recall = Recall::new();
+/// line.insert("dr");
+/// recall.handle_key(Key::Up, ModifierKey::NO_MODIFIER, &history, &mut line);
+/// assert_eq!(line.text(), "go north");
+/// recall.handle_key(Key::Up, ModifierKey::NO_MODIFIER, &history, &mut line);
+/// assert_eq!(line.text(), "take lamp");
+/// recall.handle_key(Key::Down, ModifierKey::NO_MODIFIER, &history, &mut line);
+/// recall.handle_key(Key::Down, ModifierKey::NO_MODIFIER, &history, &mut line);
+/// assert_eq!(line.text(), "dr");
+///
+/// recall.handle_key(Key::R, ModifierKey::CTRL, &history, &mut line);
+/// recall.handle_text("la", &history, &mut line);
+/// assert_eq!(line.text(), "take lamp");
+/// assert_eq!(recall.prompt(&line), Some(String::from("(reverse-i-search)`la': take lamp")));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Recall {
+    position: Option<usize>,
+    draft: String,
+    query: Option<String>,
+}
+
+impl Recall {
+    /// Starts at the end of the history, with nothing recalled.
+    pub fn new() -> Recall {
+        Recall::default()
+    }
+
+    /// Returns whether the user is searching with Ctrl+R.
+    pub fn searching(&self) -> bool {
+        self.query.is_some()
+    }
+
+    /// Applies the history command for the given key, with the given modifier keys held down.
+    /// Returns false if the key isn't a history command, so it can be used to edit the line instead.
+    /// Any key other than Ctrl+R, Backspace and the modifier keys ends a search, leaving the found input in the line.
+    ///
+    /// ```
+    /// # use piston_window::Key;
+    /// # use simpleterm::{headless::HeadlessBackend, input::ScriptedInput, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// # term.type_time = std::time::Duration::from_millis(0);
+    /// for entry in ["go north", "take lamp", "go nowhere", "Go West"].iter() {
+    ///     term.history.push(entry);
+    /// }
+    /// term.set_input_source(ScriptedInput::new()
+    ///     .press_with(Key::LCtrl, Key::R).type_text("go").press_with(Key::LCtrl, Key::R).press(Key::Return)
+    ///     .press_with(Key::LCtrl, Key::R).press(Key::LShift).type_text("G").press(Key::Return));
+    ///
+    /// assert_eq!(term.ask("$"), Some(String::from("go north")));
+    /// assert_eq!(term.ask("$"), Some(String::from("Go West")));
+    /// ```
+    pub fn handle_key(&mut self, key: Key, modifiers: ModifierKey, history: &History, line: &mut LineEditor) -> bool {
+        let ctrl: bool = modifiers.contains(ModifierKey::CTRL);
+        let plain: bool = modifiers == ModifierKey::NO_MODIFIER;
+
+        if let Some(query) = self.query.as_mut() {
+            match key {
+                Key::R if ctrl => {
+                    let before: usize = self.position.unwrap_or(history.entries().len());
+                    let query: String = query.clone();
+                    self.find(&query, before, history, line);
+                    true
+                }
+                // Pressing a modifier key on its own, like the Ctrl of the next Ctrl+R, doesn't end the search.
+                Key::LCtrl | Key::RCtrl | Key::LShift | Key::RShift | Key::LAlt | Key::RAlt => true,
+                Key::Backspace => {
+                    if let Some((i, _)) = query.grapheme_indices(true).next_back() { query.truncate(i); }
+                    let query: String = query.clone();
+                    self.find(&query, history.entries().len(), history, line);
+                    true
+                }
+                _ => {
+                    self.query = None;
+                    false
+                }
+            }
+        } else {
+            match key {
+                Key::R if ctrl => {
+                    self.query = Some(String::new());
+                    true
+                }
+                Key::Up if plain => {
+                    let position: Option<usize> = match self.position {
+                        None if !history.entries().is_empty() => {
+                            self.draft = String::from(line.text());
+                            Some(history.entries().len() - 1)
+                        }
+                        Some(position) => Some(position.saturating_sub(1)),
+                        None => None,
+                    };
+                    self.show(position, history, line);
+                    true
+                }
+                Key::Down if plain => {
+                    match self.position {
+                        Some(position) if position + 1 < history.entries().len() => self.show(Some(position + 1), history, line),
+                        Some(_) => {
+                            self.position = None;
+                            line.set_text(&self.draft);
+                        }
+                        None => {}
+                    }
+                    true
+                }
+                _ => false,
+            }
+        }
+    }
+
+    /// Adds typed text to the search query while searching, and returns whether the text was used.
+    /// Control characters are left out.
+    pub fn handle_text(&mut self, text: &str, history: &History, line: &mut LineEditor) -> bool {
+        match self.query.as_mut() {
+            Some(query) => {
+                query.extend(text.chars().filter(|c| !c.is_control()));
+                let query: String = query.clone();
+                let before: usize = self.position.map_or(history.entries().len(), |position| position + 1);
+                self.find(&query, before, history, line);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the text to show in place of the line while searching.
+    pub fn prompt(&self, line: &LineEditor) -> Option<String> {
+        self.query.as_ref().map(|query| format!("(reverse-i-search)`{}': {}", query, line.text()))
+    }
+
+    // Puts the input at the given position into the line.
+    fn show(&mut self, position: Option<usize>, history: &History, line: &mut LineEditor) {
+        if let Some(position) = position {
+            self.position = Some(position);
+            line.set_text(&history.entries()[position]);
+        }
+    }
+
+    // Puts the newest input before the given index that contains the query into the line.
+    fn find(&mut self, query: &str, before: usize, history: &History, line: &mut LineEditor) {
+        let found: Option<usize> = history.search(query, before);
+        self.show(found, history, line);
+    }
+}
@@ -0,0 +1,118 @@
+use piston_window::{Button, ButtonArgs, ButtonState, CloseArgs, Input, Key, Motion, MouseButton};
+use std::collections::VecDeque;
+
+/// An input source provides keystrokes and text to a terminal that is waiting for the user.
+///
+/// The terminal asks its input source for input before reading events from its backend,
+/// so an input source can stand in for a real keyboard.
+pub trait InputSource {
+    /// Returns the next input, or None if there is no input ready.
+    fn next_input(&mut self) -> Option<Input>;
+}
+
+/// An input source that replays a queue of keystrokes and text, for testing conversations with a terminal.
+///
+/// ```
+/// # use piston_window::Key;
+/// # use simpleterm::{headless::HeadlessBackend, input::ScriptedInput, text::*, terminal::Terminal};
+/// let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+/// term.set_input_source(ScriptedInput::new().type_text("yes").press(Key::Return).press(Key::Return));
+///
+/// assert_eq!(term.ask("Ready?"), Some(String::from("yes")));
+/// term.tell("Great!");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ScriptedInput {
+    queue: VecDeque<Input>,
+}
+
+impl ScriptedInput {
+    /// Creates an empty script.
+    pub fn new() -> ScriptedInput {
+        ScriptedInput::default()
+    }
+
+    /// Adds typed text to the end of the script.
+    pub fn type_text(mut self, text: &str) -> ScriptedInput {
+        self.queue.push_back(Input::Text(String::from(text)));
+        self
+    }
+
+    /// Adds a press and release of the given key to the end of the script.
+    pub fn press(mut self, key: Key) -> ScriptedInput {
+        for state in [ButtonState::Press, ButtonState::Release].iter() {
+            self.queue.push_back(key_input(*state, key));
+        }
+        self
+    }
+
+    /// Adds a press of the given key while the given modifier key is held down, like Shift+Up or Ctrl+V.
+    pub fn press_with(mut self, modifier: Key, key: Key) -> ScriptedInput {
+        self.queue.push_back(key_input(ButtonState::Press, modifier));
+        self = self.press(key);
+        self.queue.push_back(key_input(ButtonState::Release, modifier));
+        self
+    }
+
+    /// Adds a drag with the left mouse button from one position in the window to another.
+    pub fn drag(mut self, from: [f64; 2], to: [f64; 2]) -> ScriptedInput {
+        self.queue.push_back(Input::Move(Motion::MouseCursor(from)));
+        self.queue.push_back(mouse_input(ButtonState::Press));
+        self.queue.push_back(Input::Move(Motion::MouseCursor(to)));
+        self.queue.push_back(mouse_input(ButtonState::Release));
+        self
+    }
+
+    /// Adds a line of text followed by Return to the end of the script.
+    pub fn enter(self, line: &str) -> ScriptedInput {
+        self.type_text(line).press(Key::Return)
+    }
+
+    /// Adds a window close to the end of the script.
+    ///
+    /// ```
+    /// # use simpleterm::{headless::HeadlessBackend, input::ScriptedInput, text::*, terminal::Terminal};
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// term.set_input_source(ScriptedInput::new().type_text("n").close());
+    /// assert_eq!(term.ask("Are you there?"), None);
+    /// ```
+    pub fn close(mut self) -> ScriptedInput {
+        self.queue.push_back(Input::Close(CloseArgs));
+        self
+    }
+
+    /// Adds any other input to the end of the script.
+    pub fn push(mut self, input: Input) -> ScriptedInput {
+        self.queue.push_back(input);
+        self
+    }
+
+    /// Returns the number of inputs that haven't been replayed yet.
+    pub fn remaining(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+impl InputSource for ScriptedInput {
+    fn next_input(&mut self) -> Option<Input> {
+        self.queue.pop_front()
+    }
+}
+
+// Creates the input for pressing or releasing the given key.
+fn key_input(state: ButtonState, key: Key) -> Input {
+    Input::Button(ButtonArgs {
+        state,
+        button: Button::Keyboard(key),
+        scancode: None,
+    })
+}
+
+// Creates the input for pressing or releasing the left mouse button.
+fn mouse_input(state: ButtonState) -> Input {
+    Input::Button(ButtonArgs {
+        state,
+        button: Button::Mouse(MouseButton::Left),
+        scancode: None,
+    })
+}
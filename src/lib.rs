@@ -22,23 +22,83 @@
 
 use std::time::Duration;
 
+pub use error::Error;
+
 /// Ascii art strings.
 pub mod art;
 
+/// Provides the events a terminal reacts to and displays the frames it draws.
+pub mod backend;
+
+/// Configures and creates terminals through named settings.
+pub mod builder;
+
+/// Copies and pastes text through the system clipboard, or a clipboard inside the program.
+pub mod clipboard;
+
+/// Suggests ways to finish the user's input when they press Tab.
+pub mod completion;
+
 /// Draws rectangles and text on the terminal window.
 pub mod draw;
 
+/// Edits a line of input with a movable cursor.
+pub mod editor;
+
+/// The errors that can occur while creating or drawing a terminal.
+pub mod error;
+
+/// The results a terminal reports when it is driven from another program's event loop.
+pub mod event;
+
+/// Describes where fonts are loaded from, including the fonts bundled with simpleterm.
+pub mod font;
+
+/// Renders a terminal into an in-memory buffer, without opening a window.
+pub mod headless;
+
+/// Remembers submitted inputs so they can be recalled and searched.
+pub mod history;
+
+/// Provides keystrokes and text to a terminal from somewhere other than the keyboard.
+pub mod input;
+
+/// Menus of options the user picks from with the keyboard.
+pub mod menu;
+
+/// Finds font and art files in a list of resource folders.
+pub mod resources;
+
+/// Holds secret input like passwords, and wipes it from memory when it is no longer needed.
+pub mod secret;
+
 /// Creates and interacts with a terminal window.
 pub mod terminal;
 
+/// Renders frames offscreen and compares them against stored snapshots.
+pub mod testing;
+
 /// Contains functions related to text color and bounds.
 pub mod text;
 
+/// The results of prompts that stop waiting after a while.
+pub mod timeout;
+
+/// Limits and checks what the user types into a prompt.
+pub mod validation;
+
 /// Indicates the x and y offset of the text and surrounding box from the corners of the terminal window.
+/// This is the default for each terminal's text_offset.
 pub const TEXT_OFFSET: (f64, f64) = (25.0, 50.0);
 
 /// How long should elements like "Press Enter to Continue" or the input cursor take before toggling their flash state.
+/// This is the default for each terminal's flash_time.
 pub const FLASH_TIME: Duration = Duration::from_millis(500);
 
 /// How long should the terminal take to type a single character when displaying a message.
-pub const TYPE_TIME: Duration = Duration::from_millis(20);
\ No newline at end of file
+/// This is the default for each terminal's type_time.
+pub const TYPE_TIME: Duration = Duration::from_millis(20);
+
+/// How many lines of past messages a terminal keeps in append mode.
+/// This is the default for each terminal's scrollback.
+pub const SCROLLBACK_LINES: usize = 1000;
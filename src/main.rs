@@ -36,7 +36,7 @@ fn main() {
     term.fg_color = LIGHT_BLUE;
     term.display_art(MONA, Duration::from_millis(300));
 
-    // Change the font and colors and display some text. The window will close when the user hits enter.
+    // Change the font and colors and slowly type some text. The window will close when the user hits enter.
     term.set_font("LeagueSpartan-Regular.ttf", 30);
-    term.tell("Thus concludes the demo!");
+    term.tell_with_speed("Thus concludes the demo!", Duration::from_millis(80));
 }
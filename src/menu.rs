@@ -0,0 +1,150 @@
+use piston_window::Key;
+
+/// A list of options the user moves through with the keyboard until they give an answer.
+pub trait Menu {
+    /// What the menu returns once the user is done with it.
+    type Answer;
+
+    /// Returns the text of each option, in order.
+    fn rows(&self) -> Vec<String>;
+
+    /// Returns the index of the highlighted option.
+    fn current(&self) -> usize;
+
+    /// Applies the given key to the menu, and returns the answer if the key finishes it.
+    fn handle_key(&mut self, key: Key) -> Option<Self::Answer>;
+}
+
+/// A menu where the user picks one option. Up and Down or the number keys move between options, and Enter picks the highlighted one.
+///
+/// ```
+/// # use piston_window::Key;
+/// # use simpleterm::menu::*;
+/// let mut choice: Choice = Choice::new(&["north", "south", "east"]);
+/// assert_eq!(choice.rows(), vec![String::from("1. north"), String::from("2. south"), String::from("3. east")]);
+/// choice.handle_key(Key::Down);
+/// choice.handle_key(Key::Down);
+/// choice.handle_key(Key::Down);
+/// assert_eq!(choice.current(), 2);
+/// choice.handle_key(Key::D2);
+/// assert_eq!(choice.handle_key(Key::Return), Some(1));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Choice {
+    options: Vec<String>,
+    current: usize,
+}
+
+impl Choice {
+    /// Creates a menu of the given options, with the first one highlighted.
+    pub fn new<S: AsRef<str>>(options: &[S]) -> Choice {
+        Choice { options: options.iter().map(|option| String::from(option.as_ref())).collect(), current: 0 }
+    }
+}
+
+impl Menu for Choice {
+    type Answer = usize;
+
+    fn rows(&self) -> Vec<String> {
+        self.options.iter().enumerate().map(|(i, option)| format!("{}. {}", i + 1, option)).collect()
+    }
+
+    fn current(&self) -> usize {
+        self.current
+    }
+
+    fn handle_key(&mut self, key: Key) -> Option<usize> {
+        match key {
+            Key::Return if !self.options.is_empty() => return Some(self.current),
+            _ => self.current = moved(self.current, key, self.options.len()),
+        }
+        None
+    }
+}
+
+/// A menu where the user checks any number of options. Up and Down or the number keys move between options,
+/// Space checks or unchecks the highlighted option, A checks every option or unchecks them all if they were all checked,
+/// and Enter returns the indices of the checked options.
+///
+/// ```
+/// # use piston_window::Key;
+/// # use simpleterm::menu::*;
+/// let mut checklist: Checklist = Checklist::new(&["sound", "music", "subtitles"], &[1]);
+/// assert_eq!(checklist.rows(), vec![String::from("[ ] sound"), String::from("[x] music"), String::from("[ ] subtitles")]);
+/// checklist.handle_key(Key::D3);
+/// checklist.handle_key(Key::Space);
+/// assert_eq!(checklist.checked(), vec![1, 2]);
+/// checklist.handle_key(Key::A);
+/// assert_eq!(checklist.checked(), vec![0, 1, 2]);
+/// checklist.handle_key(Key::A);
+/// assert_eq!(checklist.handle_key(Key::Return), Some(vec![]));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Checklist {
+    options: Vec<String>,
+    checked: Vec<bool>,
+    current: usize,
+}
+
+impl Checklist {
+    /// Creates a menu of the given options, with the options at the given indices checked and the first option highlighted.
+    pub fn new<S: AsRef<str>>(options: &[S], preselected: &[usize]) -> Checklist {
+        Checklist {
+            options: options.iter().map(|option| String::from(option.as_ref())).collect(),
+            checked: (0..options.len()).map(|i| preselected.contains(&i)).collect(),
+            current: 0,
+        }
+    }
+
+    /// Returns the indices of the checked options, in order.
+    pub fn checked(&self) -> Vec<usize> {
+        self.checked.iter().enumerate().filter(|(_, checked)| **checked).map(|(i, _)| i).collect()
+    }
+}
+
+impl Menu for Checklist {
+    type Answer = Vec<usize>;
+
+    fn rows(&self) -> Vec<String> {
+        self.options.iter().zip(self.checked.iter())
+            .map(|(option, checked)| format!("[{}] {}", if *checked { "x" } else { " " }, option))
+            .collect()
+    }
+
+    fn current(&self) -> usize {
+        self.current
+    }
+
+    fn handle_key(&mut self, key: Key) -> Option<Vec<usize>> {
+        match key {
+            Key::Return => return Some(self.checked()),
+            Key::Space => if let Some(checked) = self.checked.get_mut(self.current) { *checked = !*checked; },
+            Key::A => {
+                let all: bool = self.checked.iter().all(|checked| *checked);
+                self.checked.iter_mut().for_each(|checked| *checked = !all);
+            }
+            _ => self.current = moved(self.current, key, self.options.len()),
+        }
+        None
+    }
+}
+
+// Returns the highlighted option after moving with the given key through a menu with the given number of options.
+fn moved(current: usize, key: Key, options: usize) -> usize {
+    match (key, number(key)) {
+        (Key::Up, _) => current.saturating_sub(1),
+        (Key::Down, _) => (current + 1).min(options.saturating_sub(1)),
+        (_, Some(n)) if n <= options => n - 1,
+        _ => current,
+    }
+}
+
+// Returns the number on the given number key, from 1 to 9.
+fn number(key: Key) -> Option<usize> {
+    let numbers: [(Key, Key); 9] = [
+        (Key::D1, Key::NumPad1), (Key::D2, Key::NumPad2), (Key::D3, Key::NumPad3),
+        (Key::D4, Key::NumPad4), (Key::D5, Key::NumPad5), (Key::D6, Key::NumPad6),
+        (Key::D7, Key::NumPad7), (Key::D8, Key::NumPad8), (Key::D9, Key::NumPad9),
+    ];
+    numbers.iter().position(|(digit, numpad)| key == *digit || key == *numpad).map(|i| i + 1)
+}
@@ -0,0 +1,112 @@
+use std::{env, fs, path::{Path, PathBuf}};
+
+use crate::{font::*, Error};
+
+/// The environment variable that lists extra resource folders, separated like the PATH variable.
+/// These folders are searched before any others.
+pub const RESOURCE_PATH_VAR: &str = "SIMPLETERM_RESOURCE_PATH";
+
+/// The folder that resources are looked up in when no other folders are given.
+pub const DEFAULT_RESOURCE_DIR: &str = "resources";
+
+/// An ordered list of folders to look for font and art files in.
+///
+/// ```
+/// # use std::path::Path;
+/// # use simpleterm::resources::ResourcePath;
+/// let resources: ResourcePath = ResourcePath::new(vec!["assets".into(), "resources".into()]);
+/// assert_eq!(resources.find("splash.png"), Some(Path::new("resources/splash.png").to_path_buf()));
+/// assert_eq!(resources.find("missing.png"), None);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResourcePath {
+    dirs: Vec<PathBuf>,
+}
+
+impl ResourcePath {
+    /// Creates a resource path that searches the given folders in order.
+    /// Folders listed in the SIMPLETERM_RESOURCE_PATH environment variable are searched first.
+    ///
+    /// ```
+    /// # use std::{env, path::PathBuf};
+    /// # use simpleterm::resources::*;
+    /// env::set_var(RESOURCE_PATH_VAR, "/opt/my_game/resources");
+    /// let resources: ResourcePath = ResourcePath::new(vec!["resources".into()]);
+    /// assert_eq!(resources.dirs(), &[PathBuf::from("/opt/my_game/resources"), PathBuf::from("resources")]);
+    /// ```
+    pub fn new(dirs: Vec<PathBuf>) -> ResourcePath {
+        let mut all_dirs: Vec<PathBuf> = match env::var_os(RESOURCE_PATH_VAR) {
+            Some(paths) => env::split_paths(&paths).filter(|dir| !dir.as_os_str().is_empty()).collect(),
+            None => Vec::new(),
+        };
+        all_dirs.extend(dirs);
+
+        ResourcePath { dirs: all_dirs }
+    }
+
+    /// Returns the folders this resource path searches, in order.
+    pub fn dirs(&self) -> &[PathBuf] {
+        &self.dirs
+    }
+
+    /// Adds a folder to the end of the search order.
+    pub fn push<P: Into<PathBuf>>(&mut self, dir: P) {
+        self.dirs.push(dir.into());
+    }
+
+    /// Returns the path of the first file with the given name in the searched folders.
+    /// Absolute paths are returned as they are if the file exists.
+    pub fn find<P: AsRef<Path>>(&self, name: P) -> Option<PathBuf> {
+        let name: &Path = name.as_ref();
+        if name.is_absolute() {
+            return if name.is_file() { Some(name.to_path_buf()) } else { None };
+        }
+
+        self.dirs.iter().map(|dir| dir.join(name)).find(|path| path.is_file())
+    }
+
+    /// Turns a font source into one that can be read without searching any further.
+    /// Font files are replaced by the path they were found at,
+    /// and bundled fonts are looked up like any other file if they aren't compiled into the crate.
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// # use simpleterm::{font::*, resources::ResourcePath, Error};
+    /// let resources: ResourcePath = ResourcePath::default();
+    /// let custom: FontSource = FontSource::Path("LeagueMono-Regular.ttf".into());
+    /// assert_eq!(resources.resolve_font(&custom).unwrap(), FontSource::Path(Path::new("resources/LeagueMono-Regular.ttf").into()));
+    /// assert!(matches!(resources.resolve_font(&"Missing.ttf".into()), Err(Error::FontNotFound(_))));
+    /// ```
+    pub fn resolve_font(&self, font: &FontSource) -> Result<FontSource, Error> {
+        match font {
+            #[cfg(not(feature = "embedded-fonts"))]
+            FontSource::Embedded(embedded) => self.resolve_font(&FontSource::Path(embedded.file_name().into())),
+            FontSource::Path(path) => match self.find(path) {
+                Some(found) => Ok(FontSource::Path(found)),
+                None => Err(Error::FontNotFound(path.clone())),
+            },
+            _ => Ok(font.clone()),
+        }
+    }
+
+    /// Reads the art file with the given name from the first folder that has it.
+    ///
+    /// ```
+    /// # use simpleterm::{resources::ResourcePath, Error};
+    /// let resources: ResourcePath = ResourcePath::default();
+    /// assert!(matches!(resources.read_art("missing.txt"), Err(Error::ArtNotFound(_))));
+    /// ```
+    pub fn read_art<P: AsRef<Path>>(&self, name: P) -> Result<String, Error> {
+        let name: &Path = name.as_ref();
+        self.find(name)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .ok_or_else(|| Error::ArtNotFound(name.to_path_buf()))
+    }
+}
+
+impl Default for ResourcePath {
+    /// Searches the folders in SIMPLETERM_RESOURCE_PATH, then the "resources" folder.
+    fn default() -> ResourcePath {
+        ResourcePath::new(vec![PathBuf::from(DEFAULT_RESOURCE_DIR)])
+    }
+}
@@ -0,0 +1,85 @@
+use std::{fmt, mem::MaybeUninit, ptr};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Text the user typed into a secret prompt, like a password.
+///
+/// The text is overwritten with zeroes when the secret is dropped, and is hidden when the secret is printed for debugging.
+///
+/// ```
+/// # use simpleterm::secret::Secret;
+/// let secret: Secret = Secret::from(String::from("hunter2"));
+/// assert_eq!(secret.expose(), "hunter2");
+/// assert_eq!(format!("{:?}", secret), "Secret(\"***\")");
+/// ```
+#[derive(Default, PartialEq, Eq)]
+pub struct Secret {
+    text: String,
+}
+
+impl Secret {
+    /// Returns the secret text. Avoid keeping copies of it around longer than needed.
+    pub fn expose(&self) -> &str {
+        &self.text
+    }
+}
+
+impl From<String> for Secret {
+    fn from(text: String) -> Secret {
+        Secret { text }
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Secret").field(&"***").finish()
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        zero(&mut self.text);
+    }
+}
+
+/// Overwrites every byte the given text has room for with zeroes, including text left past its end by earlier edits, and leaves it empty.
+/// ```
+/// # use simpleterm::secret::zero;
+/// let mut text: String = String::from("swordfish");
+/// zero(&mut text);
+/// assert!(text.is_empty());
+/// assert!(text.capacity() >= 9);
+/// ```
+pub fn zero(text: &mut String) {
+    text.clear();
+    zero_spare(text);
+}
+
+/// Overwrites the bytes past the end of the given text with zeroes, since text removed from a string stays in its memory.
+/// ```
+/// # use simpleterm::secret::zero_spare;
+/// let mut text: String = String::from("swordfish");
+/// text.truncate(5);
+/// zero_spare(&mut text);
+/// assert_eq!(text, "sword");
+/// ```
+pub fn zero_spare(text: &mut String) {
+    // The bytes past the end aren't part of the string, so writing zeroes there can't make it invalid UTF-8.
+    let spare: &mut [MaybeUninit<u8>] = unsafe { text.as_mut_vec() }.spare_capacity_mut();
+    for byte in spare.iter_mut() {
+        // Volatile writes keep the compiler from skipping the writes to memory that is about to be freed.
+        unsafe { ptr::write_volatile(byte, MaybeUninit::new(0)) };
+    }
+}
+
+/// Returns one mask character for each character of the given text, or nothing if there is no mask character.
+/// ```
+/// # use simpleterm::secret::mask;
+/// assert_eq!(mask("cafe\u{301}", Some('*')), "****");
+/// assert_eq!(mask("cafe\u{301}", None), "");
+/// ```
+pub fn mask(text: &str, mask_char: Option<char>) -> String {
+    match mask_char {
+        Some(mask_char) => text.graphemes(true).map(|_| mask_char).collect(),
+        None => String::new(),
+    }
+}
@@ -1,31 +1,115 @@
-use piston_window::{*, types::{Color, FontSize}};
-use std::{thread, time::{Duration, Instant}};
+use piston_window::{*, character::CharacterCache, keyboard::ModifierKey, types::{Color, FontSize}};
+use std::{collections::VecDeque, fmt::{Debug, Display}, mem, path::Path, str::FromStr, time::{Duration, Instant}};
+use unicode_segmentation::UnicodeSegmentation;
 
-use crate::{draw::*, text::*, TYPE_TIME};
+use crate::{backend::*, builder::TerminalBuilder, clipboard::*, completion::*, draw::*, editor::LineEditor, event::TerminalEvent, font::*, history::*, input::InputSource, menu::*, resources::ResourcePath, secret::*, text::*, timeout::*, validation::InputRules, Error, FLASH_TIME, SCROLLBACK_LINES, TEXT_OFFSET, TYPE_TIME};
 
-/// A terminal stores a PistonWindow, background and foreground colors,
-/// a font, fontsize, and glyph cache, and the current message and input strings.
-pub struct Terminal {
-    title: String,
+// How many lines a single step of the mouse wheel scrolls.
+const WHEEL_LINES: isize = 3;
+
+// The most bytes a secret prompt takes, so its input never has to be moved to a bigger buffer.
+const SECRET_CAPACITY: usize = 1024;
+
+/// A terminal stores a backend, background and foreground colors,
+/// a font and fontsize, and the current message and input strings.
+/// 
+/// By default the terminal is displayed in a PistonWindow, but any [Backend](../backend/trait.Backend.html) can be used.
+pub struct Terminal<B: Backend = WindowBackend> {
     active: bool,
-    /// The window that displays our terminal.
-    pub window: PistonWindow,
+    /// The backend that displays our terminal.
+    pub backend: B,
     /// The background color of our terminal.
     pub bg_color: Color,
     /// The foreground color of our terminal.
     pub fg_color: Color,
     /// Whether or not to use scanlines
     pub scanlines: bool,
-    glyphs: Glyphs,
-    font: String,
-    art_font: String,
+    font: FontSource,
+    art_font: FontSource,
     /// The font size of normal text in our terminal.
     pub font_size: FontSize,
     /// The font size of art in our terminal.
     pub art_font_size: FontSize,
+    /// The folders our terminal looks for font and art files in.
+    pub resources: ResourcePath,
+    /// How long our terminal takes to type a single character. Defaults to TYPE_TIME.
+    /// 
+    /// Typing keeps up with the time that has passed rather than waiting between characters, so the window stays responsive while it types.
+    /// 
+    /// ```
+    /// # use std::time::{Duration, Instant};
+    /// # use piston_window::{CloseArgs, Input};
+    /// # use simpleterm::{headless::HeadlessBackend, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// term.type_time = type_time_for(2.0);
+    /// term.backend.queue_input(Input::Close(CloseArgs));
+    ///
+    /// let start: Instant = Instant::now();
+    /// assert_eq!(term.ask("This message would take half a minute to type out."), None);
+    /// assert!(start.elapsed() < Duration::from_secs(5));
+    /// ```
+    pub type_time: Duration,
+    /// How long flashing elements like the input cursor take before toggling their flash state. Defaults to FLASH_TIME.
+    pub flash_time: Duration,
+    /// The x and y offset of the text from the corners of our terminal. Defaults to TEXT_OFFSET.
+    pub text_offset: (f64, f64),
+    /// Whether new messages are added below the previous ones instead of replacing them.
+    pub append_mode: bool,
+    /// How many lines of past messages our terminal keeps in append mode. Defaults to SCROLLBACK_LINES.
+    pub scrollback: usize,
+    /// Whether prompts that time out show how many seconds they have left.
+    pub countdown: bool,
+    /// Whether messages appear all at once instead of being typed out.
+    /// 
+    /// ```
+    /// # use std::time::{Duration, Instant};
+    /// # use simpleterm::{headless::HeadlessBackend, text::*, terminal::Terminal};
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// term.type_time = Duration::from_secs(1);
+    /// term.instant_text = true;
+    /// let start: Instant = Instant::now();
+    /// term.show("Already here.", Duration::from_millis(0));
+    /// assert!(start.elapsed() < Duration::from_secs(5));
+    /// ```
+    pub instant_text: bool,
+    /// The key that finishes typing out a message right away. If this is None, any key does.
+    /// The text typed by the key press that skips a prompt's message is left out of that prompt, but not out of any later one.
+    /// 
+    /// ```
+    /// # use std::time::Duration;
+    /// # use piston_window::{Button, ButtonArgs, ButtonState, Input, Key};
+    /// # use simpleterm::{headless::HeadlessBackend, input::ScriptedInput, text::*, terminal::Terminal};
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// term.type_time = Duration::from_secs(1);
+    /// let press = |key: Key| Input::Button(ButtonArgs { state: ButtonState::Press, button: Button::Keyboard(key), scancode: None });
+    /// term.backend.queue_input(press(Key::A));
+    /// term.backend.queue_input(Input::Text(String::from("a")));
+    /// term.backend.queue_input(Input::Text(String::from("ok")));
+    /// term.backend.queue_input(press(Key::Return));
+    /// assert_eq!(term.ask("Ready?"), Some(String::from("ok")));
+    ///
+    /// term.backend.queue_input(press(Key::Space));
+    /// term.set_input_source(ScriptedInput::new().press(Key::Return).type_text("yes").press(Key::Return).type_text("no").press(Key::Return));
+    /// term.tell("Skipped.");
+    /// term.type_time = Duration::from_millis(0);
+    /// assert_eq!(term.ask("Sure?"), Some(String::from("yes")));
+    /// ```
+    pub skip_key: Option<Key>,
+    /// The inputs submitted to our terminal's prompts, which the user can recall with Up and Down or search with Ctrl+R.
+    pub history: History,
     art_mode: bool,
+    art: Vec<String>,
     message: Vec<String>,
+    scroll: usize,
     input: String,
+    input_source: Option<Box<dyn InputSource>>,
+    queue: VecDeque<Queued>,
+    running: Option<Running>,
+    events: VecDeque<TerminalEvent>,
+    clipboard: Option<Box<dyn Clipboard>>,
+    error: Option<Error>,
+    skipped_by: Option<Key>,
 }
 
 impl Terminal {
@@ -36,26 +120,99 @@ impl Terminal {
     /// # use simpleterm::terminal::Terminal;
     /// let mut term: Terminal = Terminal::new("simpleterm test", (800, 600), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
     /// ```
-    pub fn new(title: &str, size: (u32, u32), bg: Color, fg: Color, font: &str, font_size: u32) -> Terminal {
-        let mut new_window: PistonWindow = WindowSettings::new(title, size).exit_on_esc(true).build().unwrap();
-        let loaded_glyphs = load_font(&mut new_window, font);
+    pub fn new<F: Into<FontSource>>(title: &str, size: (u32, u32), bg: Color, fg: Color, font: F, font_size: u32) -> Terminal {
+        Terminal::try_new(title, size, bg, fg, font, font_size).unwrap()
+    }
+
+    /// Creates a new window with the given title, colors, and font info,
+    /// or returns an error if the window can't be created or the font can't be loaded.
+    /// 
+    /// ```no_run
+    /// # use simpleterm::text::*;
+    /// # use simpleterm::terminal::Terminal;
+    /// match Terminal::try_new("simpleterm test", (800, 600), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32) {
+    ///     Ok(mut term) => term.tell("Hello!"),
+    ///     Err(e) => eprintln!("Couldn't open the terminal: {}", e),
+    /// }
+    /// ```
+    pub fn try_new<F: Into<FontSource>>(title: &str, size: (u32, u32), bg: Color, fg: Color, font: F, font_size: u32) -> Result<Terminal, Error> {
+        Terminal::try_with_backend(WindowBackend::new(title, size.into())?, bg, fg, font, font_size)
+    }
+
+    /// Returns a builder for configuring a new terminal through named settings.
+    /// 
+    /// ```no_run
+    /// # use simpleterm::text::*;
+    /// # use simpleterm::terminal::Terminal;
+    /// let mut term: Terminal = Terminal::builder().title("simpleterm test").colors(DARK_GREY, GOLD).scanlines(false).build().unwrap();
+    /// ```
+    pub fn builder() -> TerminalBuilder {
+        TerminalBuilder::new()
+    }
+}
+
+impl<B: Backend> Terminal<B> {
+    /// Creates a new terminal displayed by the given backend, with the given colors and font info
+    /// 
+    /// ```
+    /// # use simpleterm::{headless::HeadlessBackend, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((800, 600)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// ```
+    pub fn with_backend<F: Into<FontSource>>(backend: B, bg: Color, fg: Color, font: F, font_size: u32) -> Terminal<B> {
+        Terminal::try_with_backend(backend, bg, fg, font, font_size).unwrap()
+    }
 
-        Terminal {
-            title: String::from(title),
+    /// Creates a new terminal displayed by the given backend, with the given colors and font info,
+    /// or returns an error if the font can't be loaded.
+    /// 
+    /// ```
+    /// # use simpleterm::{headless::HeadlessBackend, text::*, Error};
+    /// # use simpleterm::terminal::Terminal;
+    /// let result = Terminal::try_with_backend(HeadlessBackend::new((800, 600)), DARK_GREY, GOLD, "Missing.ttf", 32);
+    /// assert!(matches!(result, Err(Error::FontNotFound(_))));
+    /// ```
+    pub fn try_with_backend<F: Into<FontSource>>(backend: B, bg: Color, fg: Color, font: F, font_size: u32) -> Result<Terminal<B>, Error> {
+        Terminal::try_with_resources(backend, ResourcePath::default(), bg, fg, font.into(), font_size)
+    }
+
+    // Creates a new terminal that looks up its fonts in the given resource folders.
+    pub(crate) fn try_with_resources(mut backend: B, resources: ResourcePath, bg: Color, fg: Color, font: FontSource, font_size: u32) -> Result<Terminal<B>, Error> {
+        load_font_into(&mut backend, &resources, &font)?;
+
+        Ok(Terminal {
             active: true,
-            window: new_window,
+            backend,
             bg_color: bg,
             fg_color: fg,
             scanlines: true,
-            glyphs: loaded_glyphs,
-            font: String::from(font),
-            art_font: String::from("LeagueMono-Regular.ttf"),
+            font,
+            art_font: EmbeddedFont::LeagueMono.into(),
             font_size,
             art_font_size: 10,
+            resources,
+            type_time: TYPE_TIME,
+            flash_time: FLASH_TIME,
+            text_offset: TEXT_OFFSET,
+            append_mode: false,
+            scrollback: SCROLLBACK_LINES,
+            countdown: false,
+            instant_text: false,
+            skip_key: None,
+            history: History::new(),
             art_mode: false,
+            art: Vec::new(),
             message: Vec::new(),
+            scroll: 0,
             input: String::default(),
-        }
+            input_source: None,
+            queue: VecDeque::new(),
+            running: None,
+            events: VecDeque::new(),
+            clipboard: None,
+            error: None,
+            skipped_by: None,
+        })
     }
 
     /// Types out the given message, then waits for the user to type something and returns Some(input string).
@@ -68,11 +225,222 @@ impl Terminal {
     /// let user_input: String = term.ask("This will wait for the user enter input!").unwrap();
     /// ```
     pub fn ask(&mut self, message: &str) -> Option<String> {
+        self.ask_with_speed(message, self.type_time)
+    }
+
+    /// Works like ask, but types the message with the given time per character instead of the terminal's type time.
+    /// 
+    /// ```
+    /// # use std::time::Duration;
+    /// # use piston_window::Key;
+    /// # use simpleterm::{headless::HeadlessBackend, input::ScriptedInput, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// term.set_input_source(ScriptedInput::new().enter("fast"));
+    /// assert_eq!(term.ask_with_speed("Which speed?", Duration::from_millis(0)), Some(String::from("fast")));
+    ///
+    /// term.set_input_source(ScriptedInput::new().type_text("cafe\u{301}!").press(Key::Backspace).press(Key::Backspace).press(Key::Return));
+    /// assert_eq!(term.ask_with_speed("Où?", Duration::from_millis(0)), Some(String::from("caf")));
+    ///
+    /// term.set_input_source(ScriptedInput::new().type_text("wrld").press(Key::Left).press(Key::Left).press(Key::Left).type_text("o")
+    ///     .press(Key::Home).type_text("hello ").press(Key::End).type_text("!").press(Key::Return));
+    /// assert_eq!(term.ask_with_speed("Greet?", Duration::from_millis(0)), Some(String::from("hello world!")));
+    /// ```
+    pub fn ask_with_speed(&mut self, message: &str, type_time: Duration) -> Option<String> {
+        let mut history: History = mem::take(&mut self.history);
+        let input: Option<String> = self.ask_using(message, type_time, Prompt::new(&mut history));
+        self.history = history;
+        input
+    }
+
+    /// Works like ask, but remembers the input in the given history instead of the terminal's history,
+    /// so each kind of prompt can recall only its own inputs.
+    /// 
+    /// To keep inputs between runs, open the history from a file and save it afterwards.
+    /// 
+    /// ```
+    /// # use std::{env, fs, path::PathBuf};
+    /// # use piston_window::Key;
+    /// # use simpleterm::{headless::HeadlessBackend, history::History, input::ScriptedInput, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// # term.type_time = std::time::Duration::from_millis(0);
+    /// let path: PathBuf = env::temp_dir().join("simpleterm-commands.txt");
+    /// # let _ = fs::remove_file(&path);
+    /// let mut commands: History = History::open(&path).unwrap();
+    /// term.set_input_source(ScriptedInput::new()
+    ///     .enter("take lamp")
+    ///     .enter("go north")
+    ///     .press(Key::Up).press(Key::Up).press(Key::Return)
+    ///     .press_with(Key::LCtrl, Key::R).type_text("nor").press(Key::Return));
+    ///
+    /// assert_eq!(term.ask_with_history("$", &mut commands), Some(String::from("take lamp")));
+    /// assert_eq!(term.ask_with_history("$", &mut commands), Some(String::from("go north")));
+    /// assert_eq!(term.ask_with_history("$", &mut commands), Some(String::from("take lamp")));
+    /// assert_eq!(term.ask_with_history("$", &mut commands), Some(String::from("go north")));
+    /// assert!(term.history.entries().is_empty());
+    ///
+    /// commands.save().unwrap();
+    /// assert_eq!(History::open(&path).unwrap().entries().len(), 4);
+    /// ```
+    pub fn ask_with_history(&mut self, message: &str, history: &mut History) -> Option<String> {
+        self.ask_using(message, self.type_time, Prompt::new(history))
+    }
+
+    /// Works like ask, but lets the user press Tab to complete their input with the given completer.
+    /// The first Tab fills in as much as all of the candidates have in common, and a second Tab lists the candidates below the message.
+    /// 
+    /// ```
+    /// # use piston_window::Key;
+    /// # use simpleterm::{completion::*, headless::HeadlessBackend, input::ScriptedInput, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// # term.type_time = std::time::Duration::from_millis(0);
+    /// let commands: WordCompleter = WordCompleter::new(&["look", "load", "quit"]);
+    /// term.set_input_source(ScriptedInput::new()
+    ///     .type_text("q").press(Key::Tab).press(Key::Return)
+    ///     .type_text("l").press(Key::Tab).press(Key::Tab).type_text("a").press(Key::Tab).press(Key::Return));
+    ///
+    /// assert_eq!(term.ask_with_completer("$", &commands), Some(String::from("quit ")));
+    /// assert_eq!(term.ask_with_completer("$", &commands), Some(String::from("load ")));
+    /// assert_eq!(term.lines().last(), Some(&String::from("look load")));
+    /// ```
+    pub fn ask_with_completer(&mut self, message: &str, completer: &dyn Completer) -> Option<String> {
+        let mut history: History = mem::take(&mut self.history);
+        let input: Option<String> = self.ask_using(message, self.type_time, Prompt { completer: Some(completer), ..Prompt::new(&mut history) });
+        self.history = history;
+        input
+    }
+
+    /// Works like ask, but keeps the user on the prompt until the given validator accepts their input.
+    /// The validator's error is shown below the message until the input is accepted, and the rejected input is left in the line to be fixed.
+    /// 
+    /// ```
+    /// # use piston_window::Key;
+    /// # use simpleterm::{headless::HeadlessBackend, input::ScriptedInput, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// # term.type_time = std::time::Duration::from_millis(0);
+    /// term.set_input_source(ScriptedInput::new().enter("xyzzy").press_with(Key::LCtrl, Key::U).enter("north"));
+    /// let direction: Option<String> = term.ask_validated("Which way?", |input| match input {
+    ///     "north" | "south" | "east" | "west" => Ok(()),
+    ///     _ => Err(format!("{} is not a direction", input)),
+    /// });
+    /// assert_eq!(direction, Some(String::from("north")));
+    /// assert_eq!(term.lines(), &[String::from("Which way?")]);
+    /// ```
+    pub fn ask_validated<F: Fn(&str) -> Result<(), String>>(&mut self, message: &str, validator: F) -> Option<String> {
+        self.ask_with_rules(message, &InputRules::new().validator(validator))
+    }
+
+    /// Works like ask, but keeps the user on the prompt until their input can be parsed into the given type, and returns the parsed value.
+    /// The latest parse error is shown below the message until the input is accepted.
+    /// 
+    /// ```
+    /// # use piston_window::Key;
+    /// # use simpleterm::{headless::HeadlessBackend, input::ScriptedInput, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// # term.type_time = std::time::Duration::from_millis(0);
+    /// term.set_input_source(ScriptedInput::new()
+    ///     .enter("many").press_with(Key::LCtrl, Key::U)
+    ///     .enter("300").press(Key::Backspace).press(Key::Backspace).press(Key::Return));
+    /// assert_eq!(term.ask_parse::<u8>("How many?"), Some(3));
+    /// assert_eq!(term.lines(), &[String::from("How many?")]);
+    /// ```
+    pub fn ask_parse<T: FromStr>(&mut self, message: &str) -> Option<T> where T::Err: Display {
+        let input: Option<String> = self.ask_validated(message, |input| input.parse::<T>().map(|_| ()).map_err(|e| e.to_string()));
+        input.and_then(|input| input.parse().ok())
+    }
+
+    /// Works like ask, but follows the given rules: characters that aren't allowed or don't fit are left out as the user types
+    /// or recalls and completes input, and the user stays on the prompt until the rules accept their input.
+    /// 
+    /// ```
+    /// # use piston_window::Key;
+    /// # use simpleterm::{headless::HeadlessBackend, input::ScriptedInput, text::*, validation::InputRules};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// # term.type_time = std::time::Duration::from_millis(0);
+    /// term.set_input_source(ScriptedInput::new().enter("ab-12 cd").enter("toolong-value").press(Key::Up).press(Key::Return));
+    /// let code: InputRules = InputRules::new().max_length(3).allowed(|c| c.is_ascii_alphanumeric());
+    /// assert_eq!(term.ask_with_rules("Code?", &code), Some(String::from("ab1")));
+    ///
+    /// term.ask("Anything?");
+    /// assert_eq!(term.ask_with_rules("Code?", &code), Some(String::from("too")));
+    /// ```
+    pub fn ask_with_rules(&mut self, message: &str, rules: &InputRules) -> Option<String> {
+        let mut history: History = mem::take(&mut self.history);
+        let input: Option<String> = self.ask_using(message, self.type_time, Prompt { rules: Some(rules), ..Prompt::new(&mut history) });
+        self.history = history;
+        input
+    }
+
+    /// Works like ask, but hides what the user types, for passwords and other secrets.
+    /// Each character is drawn as the given mask character, or nothing is drawn if there is no mask character.
+    /// 
+    /// The input isn't kept by the terminal or added to its history, and is wiped from memory when the returned Secret is dropped.
+    /// To keep it from being copied around in memory, it can be at most 1024 bytes long.
+    /// 
+    /// ```
+    /// # use simpleterm::{headless::HeadlessBackend, input::ScriptedInput, secret::Secret, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// # term.type_time = std::time::Duration::from_millis(0);
+    /// term.append_mode = true;
+    /// term.set_input_source(ScriptedInput::new().enter("open sesame"));
+    ///
+    /// let passphrase: Secret = term.ask_secret("Passphrase:", Some('*')).unwrap();
+    /// assert_eq!(passphrase.expose(), "open sesame");
+    /// assert_eq!(term.lines(), &[String::from("Passphrase:"), String::from("> ***********")]);
+    /// assert!(term.history.entries().is_empty());
+    /// ```
+    pub fn ask_secret(&mut self, message: &str, mask_char: Option<char>) -> Option<Secret> {
+        let mut history: History = History::new();
+        let input: Option<String> = self.ask_using(message, self.type_time, Prompt { secret: true, mask: mask_char, ..Prompt::new(&mut history) });
+        input.map(Secret::from)
+    }
+
+    /// Works like ask, but stops waiting after the given time and returns the given default instead.
+    /// The result says whether the user answered or time ran out. If the terminal's countdown is on, the seconds left are shown in the input line.
+    /// 
+    /// ```
+    /// # use std::time::Duration;
+    /// # use simpleterm::{headless::HeadlessBackend, input::ScriptedInput, text::*, timeout::Timed};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// # term.type_time = Duration::from_millis(0);
+    /// term.countdown = true;
+    /// term.set_input_source(ScriptedInput::new().enter("ada"));
+    /// assert_eq!(term.ask_timeout("Name?", Duration::from_secs(5), "guest"), Some(Timed::Answered(String::from("ada"))));
+    /// assert_eq!(term.ask_timeout("Name?", Duration::from_millis(100), "guest"), Some(Timed::TimedOut(String::from("guest"))));
+    /// ```
+    pub fn ask_timeout(&mut self, message: &str, time: Duration, default: &str) -> Option<Timed<String>> {
+        let mut history: History = mem::take(&mut self.history);
+        let input: Option<Timed<String>> = self.ask_timed(message, self.type_time, Prompt { timeout: Some((time, default)), ..Prompt::new(&mut history) });
+        self.history = history;
+        input
+    }
+
+    // Types out the given message and waits for input to the given prompt.
+    fn ask_using(&mut self, message: &str, type_time: Duration, prompt: Prompt) -> Option<String> {
+        self.ask_timed(message, type_time, prompt).map(Timed::into_inner)
+    }
+
+    // Types out the given message and waits for input to the given prompt, or for the prompt's timeout to run out.
+    // Secret input is returned without being kept by the terminal.
+    fn ask_timed(&mut self, message: &str, type_time: Duration, prompt: Prompt) -> Option<Timed<String>> {
         if self.active {
             self.check_text_mode();
-            self.new_message(message);
-            self.wait_for_input();
-            Some(self.input.clone())
+            self.new_message(message, type_time);
+            let (secret, mask_char): (bool, Option<char>) = (prompt.secret, prompt.mask);
+            let input: Option<Timed<String>> = self.wait_for_input(prompt);
+            if let Some(input) = input.as_ref().map(|input| match input { Timed::Answered(input) | Timed::TimedOut(input) => input }) {
+                let shown: String = if secret { mask(input, mask_char) } else { input.clone() };
+                if self.append_mode { self.append_lines(vec![format!("> {}", shown)]); }
+                if !secret { self.input = shown; }
+            }
+            input
         } else {
             None
         }
@@ -90,12 +458,34 @@ impl Terminal {
     pub fn display_art(&mut self, art: &str, time: Duration) {
         if self.active {
             self.check_art_mode();
-            self.message = art.split('\n').map(String::from).collect();
+            self.art = art.split('\n').map(String::from).collect();
             self.input = String::default();
             self.show_art(time);
         }
     }
     
+    /// Reads an ascii art file from the terminal's resource folders and displays it like display_art.
+    /// Returns an error if none of the resource folders have the file.
+    /// 
+    /// ```
+    /// # use std::{env, fs, path::PathBuf, time::Duration};
+    /// # use simpleterm::{headless::HeadlessBackend, text::*, Error};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// let art_dir: PathBuf = env::temp_dir().join("simpleterm-art");
+    /// fs::create_dir_all(&art_dir).unwrap();
+    /// fs::write(art_dir.join("smile.txt"), ":-)").unwrap();
+    ///
+    /// term.resources.push(art_dir);
+    /// assert!(term.display_art_file("smile.txt", Duration::from_millis(50)).is_ok());
+    /// assert!(matches!(term.display_art_file("frown.txt", Duration::from_millis(50)), Err(Error::ArtNotFound(_))));
+    /// ```
+    pub fn display_art_file<P: AsRef<Path>>(&mut self, name: P, time: Duration) -> Result<(), Error> {
+        let art: String = self.resources.read_art(name)?;
+        self.display_art(&art, time);
+        Ok(())
+    }
+
     /// Types out the given message, then waits for the given amount of time to continue.
     /// 
     /// ```no_run
@@ -106,9 +496,27 @@ impl Terminal {
     /// term.show("This will wait for 1 second!", Duration::from_secs(1));
     /// ```
     pub fn show(&mut self, message: &str, time: Duration) {
+        self.show_with_speed(message, time, self.type_time);
+    }
+
+    /// Works like show, but types the message with the given time per character instead of the terminal's type time.
+    /// 
+    /// ```
+    /// # use std::time::{Duration, Instant};
+    /// # use simpleterm::{headless::HeadlessBackend, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// let start: Instant = Instant::now();
+    /// term.show_with_speed("Booting...", Duration::from_millis(0), Duration::from_millis(50));
+    /// assert!(start.elapsed() >= Duration::from_millis(450));
+    ///
+    /// term.show_with_speed("Ça va? Très bien. 你好，世界！", Duration::from_millis(0), Duration::from_millis(1));
+    /// assert!(term.error().is_none());
+    /// ```
+    pub fn show_with_speed(&mut self, message: &str, time: Duration, type_time: Duration) {
         if self.active {
             self.check_text_mode();
-            self.new_message(message);
+            self.new_message(message, type_time);
             self.wait_for_timer(time);
         }
     }
@@ -122,11 +530,152 @@ impl Terminal {
     /// term.tell("This will wait for the user to hit enter!");
     /// ```
     pub fn tell(&mut self, message: &str) {
+        self.tell_with_speed(message, self.type_time);
+    }
+
+    /// Works like tell, but types the message with the given time per character instead of the terminal's type time.
+    /// 
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use simpleterm::text::*;
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal = Terminal::new("simpleterm test", (800, 600), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// term.tell_with_speed("And then... the lights went out.", Duration::from_millis(150));
+    /// ```
+    pub fn tell_with_speed(&mut self, message: &str, type_time: Duration) {
         if self.active {
             self.check_text_mode();
-            self.new_message(message);
+            self.new_message(message, type_time);
             self.input = String::from("Press Enter to Continue");
-            self.wait_for_continue();
+            self.wait_for_key(true, None, |key| if key == Key::Return { Some(()) } else { None });
+        }
+    }
+
+    /// Works like tell, but stops waiting for the user to press Enter after the given time.
+    /// Returns whether the user pressed Enter or time ran out, or None if the window is closed first.
+    /// If the terminal's countdown is on, the seconds left are shown in the input line.
+    /// 
+    /// ```
+    /// # use std::time::Duration;
+    /// # use simpleterm::{headless::HeadlessBackend, text::*, timeout::Timed};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// # term.type_time = Duration::from_millis(0);
+    /// assert_eq!(term.tell_timeout("Returning to the menu...", Duration::from_millis(100)), Some(Timed::TimedOut(())));
+    /// ```
+    pub fn tell_timeout(&mut self, message: &str, time: Duration) -> Option<Timed<()>> {
+        if self.active {
+            self.check_text_mode();
+            self.new_message(message, self.type_time);
+            self.input = String::from("Press Enter to Continue");
+            self.wait_for_key(true, Some((time, ())), |key| if key == Key::Return { Some(()) } else { None })
+        } else {
+            None
+        }
+    }
+
+    /// Types out the given message, then waits for the user to press Y or N and returns Some(true) for yes or Some(false) for no.
+    /// Pressing Enter picks the given default, which is shown capitalized in the hint next to the prompt.
+    /// If the window is closed before the user answers, returns None.
+    /// 
+    /// ```
+    /// # use piston_window::Key;
+    /// # use simpleterm::{headless::HeadlessBackend, input::ScriptedInput, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// # term.type_time = std::time::Duration::from_millis(0);
+    /// term.set_input_source(ScriptedInput::new().press(Key::X).press(Key::N).press(Key::Return).close());
+    /// assert_eq!(term.confirm("Continue?", true), Some(false));
+    /// assert_eq!(term.confirm("Continue?", true), Some(true));
+    /// assert_eq!(term.confirm("Continue?", true), None);
+    /// ```
+    pub fn confirm(&mut self, message: &str, default: bool) -> Option<bool> {
+        if self.active {
+            self.check_text_mode();
+            self.new_message(message, self.type_time);
+            self.input = String::from(if default { "[Y/n]" } else { "[y/N]" });
+            let answer: Option<bool> = self.wait_for_key(false, None, |key| match key {
+                Key::Y => Some(true),
+                Key::N => Some(false),
+                Key::Return => Some(default),
+                _ => None,
+            }).map(Timed::into_inner);
+            if let (Some(answer), true) = (answer, self.append_mode) {
+                self.append_lines(vec![String::from(if answer { "> y" } else { "> n" })]);
+            }
+            answer
+        } else {
+            None
+        }
+    }
+
+    /// Types out the given message with the given options listed below it, then waits for the user to pick one and returns Some(index of the option).
+    /// Up and Down or the number keys move the highlight between options, and Enter picks the highlighted option.
+    /// If there are no options or the window is closed before the user picks one, returns None.
+    /// 
+    /// ```
+    /// # use piston_window::Key;
+    /// # use simpleterm::{headless::HeadlessBackend, input::ScriptedInput, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// # term.type_time = std::time::Duration::from_millis(0);
+    /// term.set_input_source(ScriptedInput::new()
+    ///     .press(Key::Down).press(Key::Down).press(Key::Up).press(Key::Return)
+    ///     .press(Key::D3).press(Key::Return));
+    ///
+    /// let rooms: [&str; 3] = ["Kitchen", "Cellar", "Attic"];
+    /// assert_eq!(term.choose("Where to?", &rooms), Some(1));
+    /// assert_eq!(term.choose("Where to?", &rooms), Some(2));
+    /// assert_eq!(term.choose::<&str>("Where to?", &[]), None);
+    /// ```
+    pub fn choose<S: AsRef<str>>(&mut self, message: &str, options: &[S]) -> Option<usize> {
+        if self.active && !options.is_empty() {
+            self.check_text_mode();
+            self.new_message(message, self.type_time);
+            let choice: Option<usize> = self.wait_for_menu(&mut Choice::new(options));
+            if let (Some(choice), true) = (choice, self.append_mode) {
+                self.append_lines(vec![format!("> {}", options[choice].as_ref())]);
+            }
+            choice
+        } else {
+            None
+        }
+    }
+
+    /// Types out the given message with the given options listed below it as a checklist,
+    /// then waits for the user to check the options they want and returns Some(indices of the checked options).
+    /// The options at the preselected indices start out checked.
+    /// 
+    /// Up and Down or the number keys move the highlight between options, Space checks or unchecks the highlighted option,
+    /// A checks or unchecks every option, and Enter returns the checked options. If there are more options than fit in the terminal, the list scrolls.
+    /// If there are no options or the window is closed before the user is done, returns None.
+    /// 
+    /// ```
+    /// # use piston_window::Key;
+    /// # use simpleterm::{headless::HeadlessBackend, input::ScriptedInput, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// # term.type_time = std::time::Duration::from_millis(0);
+    /// let mut script: ScriptedInput = ScriptedInput::new().press(Key::Space);
+    /// for _ in 0..9 { script = script.press(Key::Down); }
+    /// term.set_input_source(script.press(Key::Space).press(Key::Return));
+    ///
+    /// let items: Vec<String> = (1..=10).map(|i| format!("Item {}", i)).collect();
+    /// assert_eq!(term.choose_many("Take what?", &items, &[4]), Some(vec![0, 4, 9]));
+    /// assert_eq!(term.choose_many::<&str>("Take what?", &[], &[]), None);
+    /// ```
+    pub fn choose_many<S: AsRef<str>>(&mut self, message: &str, options: &[S], preselected: &[usize]) -> Option<Vec<usize>> {
+        if self.active && !options.is_empty() {
+            self.check_text_mode();
+            self.new_message(message, self.type_time);
+            let checked: Option<Vec<usize>> = self.wait_for_menu(&mut Checklist::new(options, preselected));
+            if let (Some(checked), true) = (checked.as_ref(), self.append_mode) {
+                let names: Vec<&str> = checked.iter().map(|i| options[*i].as_ref()).collect();
+                self.append_lines(vec![format!("> {}", names.join(", "))]);
+            }
+            checked
+        } else {
+            None
         }
     }
 
@@ -139,13 +688,27 @@ impl Terminal {
     /// term.resize((800, 600).into());
     /// ```
     pub fn resize(&mut self, new_size: Size) {
+        self.try_resize(new_size).unwrap();
+    }
+
+    /// Resizes the terminal to the given (x, y) Size, or returns an error if the new window can't be created.
+    /// 
+    /// ```
+    /// # use simpleterm::{headless::HeadlessBackend, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((800, 600)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// term.try_resize((600, 800).into()).unwrap();
+    /// ```
+    pub fn try_resize(&mut self, new_size: Size) -> Result<(), Error> {
         if self.active {
-            let new_window: PistonWindow = WindowSettings::new(self.title.clone(), new_size).exit_on_esc(true).build().unwrap();
-            self.window = new_window;
+            self.backend.resize(new_size)?;
+            load_font_into(&mut self.backend, &self.resources, if self.art_mode { &self.art_font } else { &self.font })?;
         }
+        Ok(())
     }
 
-    /// Loads a new font from the given font filename and sets the given font size
+    /// Loads a new font and sets the given font size.
+    /// The font can be a bundled font's file name, a path to a font file, or the bytes of a font in memory.
     /// 
     /// ```no_run
     /// # use simpleterm::text::*;
@@ -153,15 +716,31 @@ impl Terminal {
     /// # let mut term: Terminal = Terminal::new("simpleterm test", (800, 600), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
     /// term.set_font("LeagueSpartan-Regular.ttf", 24);
     /// ```
-    pub fn set_font(&mut self, font: &str, size: FontSize) {
+    pub fn set_font<F: Into<FontSource>>(&mut self, font: F, size: FontSize) {
+        self.try_set_font(font, size).unwrap();
+    }
+
+    /// Loads a new font and sets the given font size,
+    /// or returns an error and keeps the current font if the new one can't be loaded.
+    /// 
+    /// ```
+    /// # use simpleterm::{font::*, headless::HeadlessBackend, text::*, Error};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((800, 600)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// assert!(matches!(term.try_set_font("Missing.ttf", 24), Err(Error::FontNotFound(_))));
+    /// assert!(term.try_set_font(EmbeddedFont::LeagueMono, 24).is_ok());
+    /// ```
+    pub fn try_set_font<F: Into<FontSource>>(&mut self, font: F, size: FontSize) -> Result<(), Error> {
         if self.active {
-            if !self.art_mode { self.glyphs = load_font(&mut self.window, font); }
-            self.font = String::from(font);
+            let font: FontSource = font.into();
+            if self.art_mode { read_font(&self.resources.resolve_font(&font)?)?; } else { load_font_into(&mut self.backend, &self.resources, &font)?; }
+            self.font = font;
             self.font_size = size;
         }
+        Ok(())
     }
 
-    /// Loads a new art font from the given font filename and sets the given font size.
+    /// Loads a new art font and sets the given font size.
     /// You probably want to use a mono-space font here, and a small size.
     /// 
     /// The default is LeagueMono-Regular.ttf at 10pt.
@@ -172,12 +751,100 @@ impl Terminal {
     /// # let mut term: Terminal = Terminal::new("simpleterm test", (800, 600), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
     /// term.set_art_font("LeagueMono-Regular.ttf", 10);
     /// ```
-    pub fn set_art_font(&mut self, font: &str, size: FontSize) {
+    pub fn set_art_font<F: Into<FontSource>>(&mut self, font: F, size: FontSize) {
+        self.try_set_art_font(font, size).unwrap();
+    }
+
+    /// Loads a new art font and sets the given font size,
+    /// or returns an error and keeps the current art font if the new one can't be loaded.
+    /// 
+    /// ```
+    /// # use simpleterm::{headless::HeadlessBackend, text::*, Error};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((800, 600)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// assert!(matches!(term.try_set_art_font("splash.png", 10), Err(Error::FontParse(_))));
+    /// ```
+    pub fn try_set_art_font<F: Into<FontSource>>(&mut self, font: F, size: FontSize) -> Result<(), Error> {
         if self.active {
-            if self.art_mode { self.glyphs = load_font(&mut self.window, font); }
-            self.art_font = String::from(font);
+            let font: FontSource = font.into();
+            if self.art_mode { load_font_into(&mut self.backend, &self.resources, &font)?; } else { read_font(&self.resources.resolve_font(&font)?)?; }
+            self.art_font = font;
             self.art_font_size = size;
         }
+        Ok(())
+    }
+
+    /// Removes every message from the terminal, including the scrollback kept in append mode.
+    /// 
+    /// ```
+    /// # use std::time::Duration;
+    /// # use simpleterm::{headless::HeadlessBackend, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// term.append_mode = true;
+    /// term.show_with_speed("Loading...", Duration::from_millis(0), Duration::from_millis(0));
+    /// term.show_with_speed("Done.", Duration::from_millis(0), Duration::from_millis(0));
+    /// assert_eq!(term.lines(), &[String::from("Loading..."), String::from("Done.")]);
+    ///
+    /// term.clear();
+    /// assert!(term.lines().is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.message.clear();
+        self.input = String::default();
+        self.scroll = 0;
+    }
+
+    /// Returns the lines of text currently kept by the terminal.
+    /// In append mode this is the whole scrollback, otherwise it is the last message.
+    /// 
+    /// ```
+    /// # use std::time::Duration;
+    /// # use simpleterm::{headless::HeadlessBackend, input::ScriptedInput, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// term.append_mode = true;
+    /// term.scrollback = 3;
+    /// term.set_input_source(ScriptedInput::new().enter("ls"));
+    /// term.show_with_speed("Welcome.", Duration::from_millis(0), Duration::from_millis(0));
+    /// term.ask_with_speed("$", Duration::from_millis(0));
+    /// term.show_with_speed("notes.txt", Duration::from_millis(0), Duration::from_millis(0));
+    /// assert_eq!(term.lines(), &[String::from("$"), String::from("> ls"), String::from("notes.txt")]);
+    /// ```
+    pub fn lines(&self) -> &[String] {
+        &self.message
+    }
+
+    /// Returns how many lines the user has scrolled up from the bottom of the message.
+    /// While waiting for the user, PageUp and PageDown scroll by a page, Shift+Up and Shift+Down by a line,
+    /// and the mouse wheel by three lines. The view snaps back to the bottom when a new message arrives.
+    /// 
+    /// ```
+    /// # use std::time::Duration;
+    /// # use piston_window::{Input, Key, Motion};
+    /// # use simpleterm::{headless::HeadlessBackend, input::ScriptedInput, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// term.type_time = Duration::from_millis(0);
+    /// term.append_mode = true;
+    /// for i in 0..20 {
+    ///     term.show(&format!("Line {}", i), Duration::from_millis(0));
+    /// }
+    ///
+    /// term.set_input_source(ScriptedInput::new()
+    ///     .press(Key::PageUp)
+    ///     .press_with(Key::LShift, Key::Down)
+    ///     .push(Input::Move(Motion::MouseScroll([0.0, 1.0])))
+    ///     .push(Input::Move(Motion::MouseScroll([1.0, 0.0])))
+    ///     .press(Key::Return));
+    /// term.tell("The end.");
+    /// assert_eq!(term.scroll_offset(), 7);
+    ///
+    /// term.show("New text.", Duration::from_millis(0));
+    /// assert_eq!(term.scroll_offset(), 0);
+    /// ```
+    pub fn scroll_offset(&self) -> usize {
+        self.scroll
     }
 
     /// Changes the terminal's background and foreground to the given colors. The change will be apparent in the next text command.
@@ -193,284 +860,1109 @@ impl Terminal {
         self.fg_color = fgc;
     }
 
+    /// Makes the terminal read keystrokes and text from the given input source before reading events from its backend.
+    /// The input source is only read while the terminal is waiting for the user.
+    /// 
+    /// ```
+    /// # use simpleterm::{headless::HeadlessBackend, input::ScriptedInput, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// term.set_input_source(ScriptedInput::new().enter("Ada"));
+    /// assert_eq!(term.ask("What is your name?"), Some(String::from("Ada")));
+    /// ```
+    pub fn set_input_source<I: InputSource + 'static>(&mut self, source: I) {
+        self.input_source = Some(Box::new(source));
+    }
+
+    /// Returns the clipboard the terminal copies to and pastes from, connecting to the system clipboard the first time it is used.
+    /// 
+    /// While waiting for the user, dragging the mouse over the message copies the text under it,
+    /// and Ctrl+V or Shift+Insert pastes into the input line with any newlines replaced by spaces.
+    /// 
+    /// ```
+    /// # use piston_window::Key;
+    /// # use simpleterm::{clipboard::*, headless::HeadlessBackend, input::ScriptedInput, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// # term.type_time = std::time::Duration::from_millis(0);
+    /// term.set_clipboard(MemoryClipboard::new());
+    /// term.set_input_source(ScriptedInput::new()
+    ///     .drag([0.0, 45.0], [0.0, 70.0]).press(Key::Return)
+    ///     .type_text("say ").press_with(Key::LCtrl, Key::V).press(Key::Return));
+    ///
+    /// term.tell("Hello\nworld");
+    /// assert_eq!(term.clipboard().get_text(), Some(String::from("Hello\n")));
+    /// assert_eq!(term.ask("Your words?"), Some(String::from("say Hello")));
+    /// ```
+    pub fn clipboard(&mut self) -> &mut dyn Clipboard {
+        self.clipboard.get_or_insert_with(|| Box::new(SystemClipboard::new())).as_mut()
+    }
+
+    /// Makes the terminal copy to and paste from the given clipboard instead of the system clipboard.
+    pub fn set_clipboard<C: Clipboard + 'static>(&mut self, clipboard: C) {
+        self.clipboard = Some(Box::new(clipboard));
+    }
+
+    /// Adds a message to the terminal's queue, to be typed out and shown for the given time once the messages and prompts before it are done.
+    /// 
+    /// Queued messages and prompts only move forward when [update](#method.update) is called, so the terminal can be driven from another program's event loop.
+    /// Don't mix them with the methods that wait for the user, like ask and tell.
+    pub fn push_show(&mut self, message: &str, time: Duration) {
+        self.queue.push_back(Queued::Show(String::from(message), time));
+    }
+
+    /// Adds a prompt to the terminal's queue, to be typed out once the messages and prompts before it are done.
+    /// The user's input is returned by [poll](#method.poll) as a TerminalEvent::InputSubmitted.
+    /// Like in ask, the key press that skips typing out the message isn't given to the prompt.
+    /// 
+    /// ```
+    /// # use std::time::Duration;
+    /// # use piston_window::{Button, ButtonArgs, ButtonState, Input, Key};
+    /// # use simpleterm::{backend::Backend, event::TerminalEvent, headless::HeadlessBackend, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// term.type_time = Duration::from_secs(1);
+    /// term.history.push("rm -rf build");
+    /// term.push_ask("Command?");
+    /// let press = |key: Key| Input::Button(ButtonArgs { state: ButtonState::Press, button: Button::Keyboard(key), scancode: None });
+    /// for input in vec![press(Key::Up), press(Key::Return), Input::Text(String::from("ls")), press(Key::Return)] {
+    ///     term.backend.queue_input(input);
+    /// }
+    ///
+    /// let mut result: Option<TerminalEvent> = None;
+    /// while let Some(e) = term.backend.next_event() {
+    ///     term.update(&e);
+    ///     result = term.poll();
+    ///     if result.is_some() { break; }
+    /// }
+    /// assert_eq!(result, Some(TerminalEvent::InputSubmitted(String::from("ls"))));
+    /// ```
+    pub fn push_ask(&mut self, message: &str) {
+        self.queue.push_back(Queued::Ask(String::from(message)));
+    }
+
+    /// Moves the queued messages and prompts forward with the given event.
+    /// 
+    /// Call this for every event of your own event loop, then call [poll](#method.poll) to find out what finished.
+    /// Scripted input from the terminal's input source is given to a queued prompt once it is waiting for the user, before the event.
+    /// This doesn't draw anything: call [render](#method.render) or [draw](#method.draw) for that.
+    /// 
+    /// ```
+    /// # use std::time::Duration;
+    /// # use piston_window::{Button, ButtonArgs, ButtonState, Event, Input, Key};
+    /// # use simpleterm::{backend::Backend, event::TerminalEvent, headless::HeadlessBackend, input::ScriptedInput, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// term.instant_text = true;
+    /// term.push_show("Welcome back.", Duration::from_millis(50));
+    /// term.push_ask("Name?");
+    /// term.push_ask("Quest?");
+    /// term.set_input_source(ScriptedInput::new().enter("grail"));
+    ///
+    /// let mut results: Vec<TerminalEvent> = Vec::new();
+    /// while let Some(e) = term.backend.next_event() {
+    ///     term.update(&e);
+    ///     term.render(&e);
+    ///     while let Some(result) = term.poll() {
+    ///         if result == TerminalEvent::MessageShown {
+    ///             term.backend.queue_input(Input::Text(String::from("ada")));
+    ///             term.backend.queue_input(Input::Button(ButtonArgs { state: ButtonState::Press, button: Button::Keyboard(Key::Return), scancode: None }));
+    ///         }
+    ///         results.push(result);
+    ///     }
+    ///     if term.is_idle() { break; }
+    /// }
+    /// assert_eq!(results, vec![
+    ///     TerminalEvent::MessageShown,
+    ///     TerminalEvent::InputSubmitted(String::from("grail")),
+    ///     TerminalEvent::InputSubmitted(String::from("ada")),
+    /// ]);
+    /// ```
+    pub fn update(&mut self, e: &Event) {
+        if !self.active { return; }
+        if e.close_args().is_some() {
+            self.active = false;
+            self.queue.clear();
+            self.running = None;
+            self.events.push_back(TerminalEvent::Closed);
+            return;
+        }
+
+        while let Some(mut running) = self.running.take().or_else(|| self.start_next()) {
+            let mut done: bool = false;
+            while !done && running.is_waiting() {
+                match self.input_source.as_mut().and_then(|source| source.next_input()) {
+                    Some(input) => done = self.update_running(&Event::Input(input, None), &mut running),
+                    None => break,
+                }
+            }
+            // If scripted input finished the prompt, the event goes to whatever is queued next.
+            if done { continue; }
+
+            if !self.update_running(e, &mut running) { self.running = Some(running); }
+            break;
+        }
+    }
+
+    /// Draws the terminal as it was left by the last [update](#method.update) through its own backend, if the given event is a render event.
+    pub fn render(&mut self, e: &Event) {
+        if !self.active || e.render_args().is_none() { return; }
+        let shown: QueueFrame = self.queue_frame();
+        let result: Result<(), Error> = self.backend.render(e, &shown.frame());
+        self.stop_on_error(result);
+    }
+
+    /// Draws the terminal as it was left by the last [update](#method.update) with the given glyphs and graphics,
+    /// so it can be drawn inside another program's window. The terminal fills the size of its backend.
+    /// 
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use piston_window::*;
+    /// # use simpleterm::{headless::HeadlessBackend, text::*, terminal::Terminal};
+    /// let mut window: PistonWindow = WindowSettings::new("My game", (800, 600)).build().unwrap();
+    /// let mut glyphs: Glyphs = window.load_font("resources/LeagueSpartan-Regular.ttf").unwrap();
+    /// let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((800, 600)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// term.push_show("Hello from inside the game.", Duration::from_secs(3));
+    ///
+    /// while let Some(e) = window.next() {
+    ///     term.update(&e);
+    ///     window.draw_2d(&e, |c, g, device| {
+    ///         term.draw(&mut glyphs, c, g).unwrap();
+    ///         glyphs.factory.encoder.flush(device);
+    ///     });
+    /// }
+    /// ```
+    pub fn draw<C, G>(&self, glyphs: &mut C, context: Context, graphics: &mut G) -> Result<(), Error>
+    where
+        C: CharacterCache,
+        C::Error: Debug,
+        G: Graphics<Texture = C::Texture>,
+    {
+        draw_frame(&self.queue_frame().frame(), self.backend.size(), glyphs, context, graphics)
+    }
+
+    /// Returns the next thing the terminal finished while it was being updated, or None if nothing new has finished.
+    pub fn poll(&mut self) -> Option<TerminalEvent> {
+        self.events.pop_front()
+    }
+
+    /// Returns whether the terminal has no queued messages or prompts left to work on.
+    pub fn is_idle(&self) -> bool {
+        self.queue.is_empty() && self.running.is_none()
+    }
+
+    /// Returns the error that stopped the terminal, if the terminal stopped because something went wrong while drawing.
+    /// 
+    /// ```
+    /// # use std::time::Duration;
+    /// # use simpleterm::{headless::HeadlessBackend, text::*};
+    /// # use simpleterm::terminal::Terminal;
+    /// # let mut term: Terminal<HeadlessBackend> = Terminal::with_backend(HeadlessBackend::new((320, 240)), DARK_GREY, GOLD, "LeagueSpartan-Regular.ttf", 32);
+    /// term.show("Everything is fine.", Duration::from_millis(50));
+    /// assert!(term.error().is_none());
+    /// ```
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+
     fn check_art_mode(&mut self) {
         if !self.art_mode {
-            self.glyphs = load_font(&mut self.window, &self.art_font);
+            let loaded: Result<(), Error> = load_font_into(&mut self.backend, &self.resources, &self.art_font);
+            self.stop_on_error(loaded);
             self.art_mode = true;
         }
     }
 
     fn check_text_mode(&mut self) {
         if self.art_mode {
-            self.glyphs = load_font(&mut self.window, &self.font);
+            let loaded: Result<(), Error> = load_font_into(&mut self.backend, &self.resources, &self.font);
+            self.stop_on_error(loaded);
             self.art_mode = false;
         }
     }
 
+    // Deactivates the terminal and keeps the error if the given result is an error.
+    fn stop_on_error(&mut self, result: Result<(), Error>) {
+        if let Err(e) = result {
+            self.active = false;
+            self.error = Some(e);
+        }
+    }
+
     // Displays an art string along with the rest of the terminal.
     fn show_art(&mut self, timer: Duration) {
-        let bgc: Color = self.bg_color;
-        let fgc: Color = self.fg_color;
-
-        let art: &Vec<String> = &self.message;
-        let glyphs: &mut Glyphs = &mut self.glyphs;
-        let font_size: FontSize = self.art_font_size;
-        let use_filter: bool = self.scanlines;
+        let frame: Frame = Frame {
+            bg_color: self.bg_color,
+            fg_color: self.fg_color,
+            scanlines: self.scanlines,
+            font_size: self.art_font_size,
+            text_offset: self.text_offset,
+            contents: Contents::Art(&self.art),
+        };
         
         let start: Instant = Instant::now();
         let mut active: bool = self.active;
-        while let Some(e) = self.window.next() {
+        let mut result: Result<(), Error> = Ok(());
+        while let Some(e) = self.backend.next_event() {
             e.close(|_| { active = false; });
 
-            let win_size: Size = self.window.window.size();
-
             let now: Instant = Instant::now();
             if now.duration_since(start) > timer { break; }
 
-            self.window.draw_2d(&e, |c, g, device| {
-                clear(bgc, g);
-
-                draw_background(win_size, bgc, fgc, use_filter, c, g);
-                draw_art(win_size, art, glyphs, font_size, fgc, c, g);
-                draw_foreground(win_size, bgc, use_filter, c, g);
-            
-                glyphs.factory.encoder.flush(device);
-            });
+            if let Err(error) = self.backend.render(&e, &frame) { result = Err(error); break; }
         }
         self.active = active;
+        self.stop_on_error(result);
     }
 
-    // Types a message one character at a time, waiting TYPE_TIME between each character.
-    fn type_message(&mut self) {
+    // Types the lines of the message from first_line onwards one character at a time, typing one character every type time.
+    // How much is typed depends on the time since typing started, not on how often the backend gives out events.
+    // The lines before first_line are shown as they are.
+    fn type_message(&mut self, type_time: Duration, first_line: usize) {
         let bgc: Color = self.bg_color;
         let fgc: Color = self.fg_color;
-        let current_input: &str = &(self.input[..]);
-        let glyphs = &mut self.glyphs;
+        let current_input: String = self.input.clone();
         let font_size: FontSize = self.font_size;
+        let text_offset: (f64, f64) = self.text_offset;
 
-        let mut typed_message: Vec<String> = Vec::new();
+        let rows: usize = visible_lines(self.backend.size(), text_offset, font_size);
         let use_filter: bool = self.scanlines;
 
+        let ends: Vec<(usize, usize)> = grapheme_ends(&self.message, first_line);
+        if ends.is_empty() { return; }
+
+        let start: Instant = Instant::now();
         let mut active: bool = self.active;
-        for (i, line) in self.message.iter().enumerate() {
-            typed_message.push(String::default());
-
-            let line_len: usize = line.len();
-            for j in 1..line_len {
-                typed_message[i] = String::from(&line[..=j]);
-                typed_message[i].push_str("[]");
-                if let Some(e) = self.window.next() {
-                    e.close(|_| { active = false; });
-
-                    let win_size: Size = self.window.window.size();
-
-                    self.window.draw_2d(&e, |c, g, device| {
-                        clear(bgc, g);
-
-                        draw_background(win_size, bgc, fgc, use_filter, c, g);
-                        draw_message(&typed_message, glyphs, font_size, fgc, c, g);
-                        draw_input(win_size, current_input, glyphs, font_size, fgc, c, g);
-                        draw_foreground(win_size, bgc, use_filter, c, g);
-                    
-                        glyphs.factory.encoder.flush(device);
-                    });
-                    thread::sleep(TYPE_TIME);
-                }
-                typed_message[i].pop();
-                typed_message[i].pop();
-            }
+        let mut result: Result<(), Error> = Ok(());
+        while let Some(e) = self.backend.next_event() {
+            e.close(|_| { active = false; });
+            if !active { break; }
+
+            if self.skips_typing(&e) { break; }
+            if e.render_args().is_none() { continue; }
+
+            let typed: usize = typed_count(start.elapsed(), type_time, ends.len());
+            let typed_message: Vec<String> = typed_lines(&self.message, &ends, typed);
+
+            let view: (&[String], ScrollPosition) = view(&typed_message, rows, 0);
+            result = self.backend.render(&e, &Frame {
+                bg_color: bgc,
+                fg_color: fgc,
+                scanlines: use_filter,
+                font_size,
+                text_offset,
+                contents: Contents::Text { message: view.0, marker: false, input: Some(&current_input), cursor: None, scroll: Some(view.1), selection: None, highlight: None, status: None },
+            });
+            if result.is_err() || typed == ends.len() { break; }
         }
         self.active = active;
+        self.stop_on_error(result);
     }
 
-    // Displays the current terminal until the user presses Enter.
-    fn wait_for_continue(&mut self) {
-        let mut ready: bool = false;
+    // Returns whether the event skips typing out the message: a press of the skip key, or of any key if there is no skip key.
+    fn skips_typing(&mut self, e: &Event) -> bool {
+        let skip_key: Option<Key> = self.skip_key;
+        let mut skipped_by: Option<Key> = None;
+        e.button(|button_args| {
+            if let (ButtonState::Press, Button::Keyboard(key)) = (button_args.state, button_args.button) {
+                if skip_key.is_none() || skip_key == Some(key) { skipped_by = Some(key); }
+            }
+        });
+        if skipped_by.is_some() { self.skipped_by = skipped_by; }
+        skipped_by.is_some()
+    }
+
+    // Returns whether the event is the text typed by the key press that skipped typing out the message, which is left out of the prompt.
+    // That text comes after the key press and before its release, so once another key is pressed or the key is released, no text is left out.
+    fn is_skip_text(&mut self, e: &Event) -> bool {
+        let skipped_by: Key = match self.skipped_by {
+            Some(key) => key,
+            None => return false,
+        };
+        let text: bool = e.text(|_| ()).is_some();
+        let mut done: bool = text;
+        e.button(|button_args| {
+            if let Button::Keyboard(key) = button_args.button {
+                done = button_args.state == ButtonState::Press || key == skipped_by;
+            }
+        });
+        if done { self.skipped_by = None; }
+        text
+    }
+
+    // Displays the current terminal, with the input flashing if asked, until the given function returns an answer for a key the user pressed.
+    // If a timeout is given, the given default answer is returned once it runs out.
+    // Returns None if the window is closed or can't be drawn first.
+    fn wait_for_key<T, F: FnMut(Key) -> Option<T>>(&mut self, flashing: bool, timeout: Option<(Duration, T)>, mut answer: F) -> Option<Timed<T>> {
+        let mut ready: Option<Timed<T>> = None;
+        let (deadline, mut default): (Option<Instant>, Option<T>) = match timeout {
+            Some((time, default)) => (Some(Instant::now() + time), Some(default)),
+            None => (None, None),
+        };
 
         let bgc: Color = self.bg_color;
         let fgc: Color = self.fg_color;
 
-        let message: &Vec<String> = &self.message;
-        let current_input: &str = &(self.input);
-        let glyphs: &mut Glyphs = &mut self.glyphs;
+        let current_input: String = self.input.clone();
         let font_size: FontSize = self.font_size;
+        let text_offset: (f64, f64) = self.text_offset;
+        let rows: usize = visible_lines(self.backend.size(), text_offset, font_size);
+        let flash_time: Duration = self.flash_time;
         let use_filter: bool = self.scanlines;
         
+        let mut dragging: Dragging = Dragging::default();
+        let mut modifiers: ModifierKey = ModifierKey::default();
         let mut start: Instant = Instant::now();
         let mut active: bool = self.active;
-        while let Some(e) = self.window.next() {
+        let mut result: Result<(), Error> = Ok(());
+        while let Some(e) = next_event(&mut self.backend, &mut self.input_source) {
             e.close(|_| { active = false; });
+            if !active { break; }
 
-            let win_size: Size = self.window.window.size();
+            modifiers.event(&e);
+            self.scroll = scrolled(self.scroll, scroll_amount(&e, modifiers, rows), self.message.len(), rows);
+            if let Err(error) = self.drag_selection(&e, &mut dragging, rows) { result = Err(error); break; }
 
             e.button(|button_args| {
                 if let Button::Keyboard(key) = button_args.button {
-                    if button_args.state == ButtonState::Press && key == Key::Return { ready = true; }
+                    if button_args.state == ButtonState::Press { ready = answer(key).map(Timed::Answered); }
                 }
             });
 
-            if ready { break; }
-
             let now: Instant = Instant::now();
-            self.window.draw_2d(&e, |c, g, device| {
-                clear(bgc, g);
-
-                draw_background(win_size, bgc, fgc, use_filter, c, g);
-                draw_message(message, glyphs, font_size, fgc, c, g);
-                draw_input_marker(win_size, glyphs, font_size, fgc, c, g);
-                if check_flash(now, &mut start) { draw_input(win_size, current_input, glyphs, font_size, fgc, c, g); }
-                draw_foreground(win_size, bgc, use_filter, c, g);
-            
-                glyphs.factory.encoder.flush(device);
+            if ready.is_none() && deadline.is_some_and(|deadline| now >= deadline) { ready = default.take().map(Timed::TimedOut); }
+            if ready.is_some() { break; }
+
+            let flash: bool = e.render_args().is_some() && check_flash_every(now, &mut start, flash_time);
+            let (message, position): (&[String], ScrollPosition) = view(&self.message, rows, self.scroll);
+            let status: Option<String> = deadline.filter(|_| self.countdown).map(|deadline| countdown(deadline.saturating_duration_since(now)));
+            result = self.backend.render(&e, &Frame {
+                bg_color: bgc,
+                fg_color: fgc,
+                scanlines: use_filter,
+                font_size,
+                text_offset,
+                contents: Contents::Text {
+                    message,
+                    marker: true,
+                    input: if flash || !flashing { Some(&current_input) } else { None },
+                    cursor: None,
+                    scroll: Some(position),
+                    selection: dragging.shown(position),
+                    highlight: None,
+                    status: status.as_deref(),
+                },
             });
+            if result.is_err() { break; }
         }
         self.active = active;
+        self.stop_on_error(result);
+        if self.active { ready } else { None }
     }
 
-    // Displays the current terminal until the user submits some input.
-    fn wait_for_input(&mut self) {
-        let mut input_string: String = String::default();
+    // Displays the current terminal until the user submits some input, and returns the input.
+    // If the prompt has a timeout, its default is returned once it runs out.
+    // Returns None if the window is closed or can't be drawn first.
+    fn wait_for_input(&mut self, prompt: Prompt) -> Option<Timed<String>> {
+        let (secret, mask_char, timeout): (bool, Option<char>, Option<(Duration, &str)>) = (prompt.secret, prompt.mask, prompt.timeout);
+        let deadline: Option<Instant> = timeout.map(|(time, _)| Instant::now() + time);
+        let mut editing: InputLine = InputLine::new(if secret { LineEditor::with_capacity(SECRET_CAPACITY) } else { LineEditor::new() });
+        let mut dragging: Dragging = Dragging::default();
         let mut input_accepted: bool = false;
+        let mut input: Option<Timed<String>> = None;
+        let mut error: Vec<String> = Vec::new();
 
         let bgc: Color = self.bg_color;
         let fgc: Color = self.fg_color;
 
-        let message: &Vec<String> = &self.message;
-        let glyphs: &mut Glyphs = &mut self.glyphs;
         let font_size: FontSize = self.font_size;
+        let text_offset: (f64, f64) = self.text_offset;
+        let rows: usize = visible_lines(self.backend.size(), text_offset, font_size);
+        let flash_time: Duration = self.flash_time;
         let use_filter: bool = self.scanlines;
         
+        let mut modifiers: ModifierKey = ModifierKey::default();
         let mut start: Instant = Instant::now();
         let mut active: bool = self.active;
-        while let Some(e) = self.window.next() {
+        let mut result: Result<(), Error> = Ok(());
+        while let Some(e) = next_event(&mut self.backend, &mut self.input_source) {
             e.close(|_| { active = false; });
+            if !active { break; }
+            if self.is_skip_text(&e) { continue; }
 
-            let win_size: Size = self.window.window.size();
+            modifiers.event(&e);
+            self.scroll = scrolled(self.scroll, scroll_amount(&e, modifiers, rows), self.message.len(), rows);
+            if let Err(error) = self.drag_selection(&e, &mut dragging, rows) { result = Err(error); break; }
             
-            e.text(|text| input_string.push_str(text));
-            e.button(|button_args| {
-                if let Button::Keyboard(key) = button_args.button {
-                    if button_args.state == ButtonState::Press {
-                        if key == Key::Backspace { input_string.pop(); }
-                        if key == Key::Return && input_string != "" { input_accepted = true; }
-                    }
-                }
-            });
-
+            let rejected: Option<String> = match self.edit_input(&e, modifiers, &mut editing, &prompt) {
+                Some(Ok(())) => { input_accepted = true; None }
+                Some(Err(error)) => Some(error),
+                None => None,
+            };
             if input_accepted {
-                self.input = input_string.clone();
-                input_string = String::default();
+                if !secret { prompt.history.push(editing.line.text()); }
+                input = Some(Timed::Answered(editing.line.take()));
+                editing.recall = Recall::new();
             }
-            
+
             let now: Instant = Instant::now();
-            self.window.draw_2d(&e, |c, g, device| {
-                clear(bgc, g);
-
-                draw_background(win_size, bgc, fgc, use_filter, c, g);
-                draw_message(message, glyphs, font_size, fgc, c, g);
-                draw_input_marker(win_size, glyphs, font_size, fgc, c, g);
-
-                if check_flash(now, &mut start) {
-                    input_string.push_str("[]");
-                    draw_input(win_size, &input_string[..], glyphs, font_size, fgc, c, g);
-                    input_string.pop();
-                    input_string.pop();
-                } else {
-                    draw_input(win_size, &input_string[..], glyphs, font_size, fgc, c, g);
-                }
-                
-                draw_foreground(win_size, bgc, use_filter, c, g);
-            
-                glyphs.factory.encoder.flush(device);
+            if let (None, Some((deadline, (_, default)))) = (input.as_ref(), deadline.zip(timeout)) {
+                if now >= deadline { input = Some(Timed::TimedOut(String::from(default))); }
+            }
+            if rejected.is_some() || input.is_some() { error = self.replace_note(&error, rejected.as_deref()); }
+            if input.is_some() && !input_accepted { break; }
+
+            let status: Option<String> = deadline.filter(|_| self.countdown).map(|deadline| countdown(deadline.saturating_duration_since(now)));
+            let flash: bool = e.render_args().is_some() && check_flash_every(now, &mut start, flash_time);
+            let (message, position): (&[String], ScrollPosition) = view(&self.message, rows, self.scroll);
+            let line: &LineEditor = &editing.line;
+            let search: Option<String> = editing.recall.prompt(line);
+            let masked: Option<String> = if secret { Some(mask(line.text(), mask_char)) } else { None };
+            let cursor: usize = if secret { mask(&line.text()[..line.cursor()], mask_char).len() } else { line.cursor() };
+            result = self.backend.render(&e, &Frame {
+                bg_color: bgc,
+                fg_color: fgc,
+                scanlines: use_filter,
+                font_size,
+                text_offset,
+                contents: Contents::Text {
+                    message,
+                    marker: true,
+                    input: Some(masked.as_deref().or(search.as_deref()).unwrap_or(line.text())),
+                    cursor: if flash && search.is_none() { Some(cursor) } else { None },
+                    scroll: Some(position),
+                    selection: dragging.shown(position),
+                    highlight: None,
+                    status: status.as_deref(),
+                },
             });
 
-            if input_accepted { break; }
+            if input_accepted || result.is_err() { break; }
         }
+        if secret { zero(&mut editing.line.take()); }
         self.active = active;
+        self.stop_on_error(result);
+        if self.active {
+            input
+        } else {
+            // Input accepted right before the window closed is thrown away, so secret input has to be wiped here.
+            if let (true, Some(input)) = (secret, input) { zero(&mut input.into_inner()); }
+            None
+        }
     }
 
-    // Displays an the current terminal until the timer runs out.
-    fn wait_for_timer(&mut self, timer: Duration) {
+    // Applies the text, key presses and pastes of the given event to the input line of the given prompt.
+    // Returns what pressing Return did, if it was pressed with some input: Ok if the input was accepted, or the error the prompt's rules gave.
+    fn edit_input(&mut self, e: &Event, modifiers: ModifierKey, editing: &mut InputLine, prompt: &Prompt) -> Option<Result<(), String>> {
+        let (history, completer, secret, rules): (&History, Option<&dyn Completer>, bool, Option<&InputRules>) = (prompt.history, prompt.completer, prompt.secret, prompt.rules);
+        let InputLine { line, recall, tabbed } = editing;
+
+        if e.text(|text| recall_or_insert(line, text, secret, recall, history, rules)).is_some() { self.scroll = 0; }
+        let mut submitted: Option<Result<(), String>> = None;
+        let mut candidates: Option<Vec<String>> = None;
+        let mut paste: bool = false;
+        e.button(|button_args| {
+            if let Button::Keyboard(key) = button_args.button {
+                if button_args.state == ButtonState::Press {
+                    if key == Key::Return {
+                        if !line.text().is_empty() { submitted = Some(rules.map_or(Ok(()), |rules| rules.check(line.text()))); }
+                    } else if is_paste(key, modifiers) {
+                        paste = true;
+                    } else if let (Key::Tab, Some(completer)) = (key, completer) {
+                        candidates = complete(completer, line, *tabbed);
+                        refit(line, rules);
+                    } else if !secret && recall.handle_key(key, modifiers, history, line) {
+                        refit(line, rules);
+                    } else {
+                        line.handle_key(key, modifiers);
+                    }
+                    *tabbed = key == Key::Tab;
+                }
+            }
+        });
+        if let Some(candidates) = candidates { self.append_note(&candidates.join(" ")); }
+        if let Some(text) = if paste { self.clipboard().get_text() } else { None } {
+            recall_or_insert(line, &single_line(&text), secret, recall, history, rules);
+            self.scroll = 0;
+        }
+        submitted
+    }
+
+    // Displays the current terminal with the given menu below the message, until the user finishes the menu.
+    // The highlighted option flashes between inverted colors and a lighter highlight, and the view follows it.
+    // Returns None if the window is closed or can't be drawn first.
+    fn wait_for_menu<M: Menu>(&mut self, menu: &mut M) -> Option<M::Answer> {
+        let mut answer: Option<M::Answer> = None;
+
         let bgc: Color = self.bg_color;
         let fgc: Color = self.fg_color;
 
-        let message: &Vec<String> = &self.message;
-        let glyphs: &mut Glyphs = &mut self.glyphs;
         let font_size: FontSize = self.font_size;
+        let text_offset: (f64, f64) = self.text_offset;
+        let rows: usize = visible_lines(self.backend.size(), text_offset, font_size);
+        let flash_time: Duration = self.flash_time;
         let use_filter: bool = self.scanlines;
+
+        let mut scroll: usize = 0;
+        let mut start: Instant = Instant::now();
+        let mut active: bool = self.active;
+        let mut result: Result<(), Error> = Ok(());
+        while let Some(e) = next_event(&mut self.backend, &mut self.input_source) {
+            e.close(|_| { active = false; });
+            if !active { break; }
+
+            e.button(|button_args| {
+                if let (ButtonState::Press, Button::Keyboard(key)) = (button_args.state, button_args.button) {
+                    answer = menu.handle_key(key);
+                }
+            });
+
+            if answer.is_some() { break; }
+
+            let mut lines: Vec<String> = self.message.clone();
+            let highlighted: usize = lines.len() + menu.current();
+            lines.extend(menu.rows());
+            scroll = following(scroll, highlighted, lines.len(), rows);
+
+            let now: Instant = Instant::now();
+            let flash: bool = e.render_args().is_some() && check_flash_every(now, &mut start, flash_time);
+            let (message, position): (&[String], ScrollPosition) = view(&lines, rows, scroll);
+            let row: usize = highlighted - position.first;
+            result = self.backend.render(&e, &Frame {
+                bg_color: bgc,
+                fg_color: fgc,
+                scanlines: use_filter,
+                font_size,
+                text_offset,
+                contents: Contents::Text {
+                    message,
+                    marker: false,
+                    input: None,
+                    cursor: None,
+                    scroll: Some(position),
+                    selection: if flash { None } else { Some(Selection { start: (row, 0), end: (row, usize::MAX) }) },
+                    highlight: if flash { Some(row) } else { None },
+                    status: None,
+                },
+            });
+            if result.is_err() { break; }
+        }
+        self.active = active;
+        self.stop_on_error(result);
+        if self.active { answer } else { None }
+    }
+
+    // Displays an the current terminal until the timer runs out.
+    fn wait_for_timer(&mut self, timer: Duration) {
+        let (message, position): (&[String], ScrollPosition) = view(&self.message, visible_lines(self.backend.size(), self.text_offset, self.font_size), 0);
+        let frame: Frame = Frame {
+            bg_color: self.bg_color,
+            fg_color: self.fg_color,
+            scanlines: self.scanlines,
+            font_size: self.font_size,
+            text_offset: self.text_offset,
+            contents: Contents::Text { message, marker: false, input: None, cursor: None, scroll: Some(position), selection: None, highlight: None, status: None },
+        };
         
         let start: Instant = Instant::now();
         let mut active: bool = self.active;
-        while let Some(e) = self.window.next() {
+        let mut result: Result<(), Error> = Ok(());
+        while let Some(e) = self.backend.next_event() {
             e.close(|_| { active = false; });
 
-            let win_size: Size = self.window.window.size();
-
             let now: Instant = Instant::now();
             if now.duration_since(start) > timer { break; }
 
-            self.window.draw_2d(&e, |c, g, device| {
-                clear(bgc, g);
-
-                draw_background(win_size, bgc, fgc, use_filter, c, g);
-                draw_message(message, glyphs, font_size, fgc, c, g);
-                draw_foreground(win_size, bgc, use_filter, c, g);
-            
-                glyphs.factory.encoder.flush(device);
-            });
+            if let Err(error) = self.backend.render(&e, &frame) { result = Err(error); break; }
         }
         self.active = active;
+        self.stop_on_error(result);
+    }
+
+    // Wraps a new message to fit the current window's bounds and types it out.
+    fn new_message(&mut self, message: &str, type_time: Duration) {
+        self.skipped_by = None;
+        if let Some(first_line) = self.set_message(message) {
+            if !self.instant_text { self.type_message(type_time, first_line); }
+        }
     }
 
-    // Processes a new message and types it out.
-    fn new_message(&mut self, message: &str) {
-        self.message = message.split('\n').map(String::from).collect();
-        self.process_message();
+    // Wraps a new message to fit the current window's bounds and adds it to the terminal without typing it out.
+    // Returns the index of the message's first line, or None if the message couldn't be wrapped.
+    fn set_message(&mut self, message: &str) -> Option<usize> {
+        let max_width: f64 = self.backend.size().width - self.text_offset.0 * 2.0;
+        let font_size: FontSize = self.font_size;
+        let backend: &mut B = &mut self.backend;
+
+        let lines: Vec<String> = match wrap_text_by(message, max_width, |text| backend.text_width(text, font_size)) {
+            Ok(lines) => lines,
+            Err(e) => { self.stop_on_error(Err(e)); return None; }
+        };
+
+        let new_lines: usize = lines.len();
+        if self.append_mode { self.append_lines(lines); } else { self.message = lines; }
         self.input = String::default();
-        self.type_message();
+        self.scroll = 0;
+        Some(self.message.len().saturating_sub(new_lines))
     }
 
-    // Splits a message into a vector of strings that can fit in the current window's bounds.
-    fn process_message(&mut self) {
-        let max_chars: usize = self.get_max_characters();
+    // Takes the next message or prompt from the queue and adds its message to the terminal.
+    fn start_next(&mut self) -> Option<Running> {
+        let (message, stage): (String, Stage) = match self.queue.pop_front()? {
+            Queued::Show(message, time) => (message, Stage::Show { time, until: None }),
+            Queued::Ask(message) => (message, Stage::Ask { editing: InputLine::new(LineEditor::new()) }),
+        };
 
-        let mut new_message_vec: Vec<String> = Vec::new();
+        self.check_text_mode();
+        self.skipped_by = None;
+        let first_line: usize = self.set_message(&message)?;
+        let ends: Vec<(usize, usize)> = grapheme_ends(&self.message, first_line);
+        let typing: Option<Typing> = if self.instant_text || ends.is_empty() {
+            None
+        } else {
+            Some(Typing { start: Instant::now(), type_time: self.type_time, ends })
+        };
+        Some(Running { stage, typing, modifiers: ModifierKey::default(), flash_start: Instant::now(), flash: false })
+    }
 
-        for old_message in self.message.iter() {
-            let mut new_message: String = String::new();
+    // Moves the running message or prompt forward with the given event. Returns whether it is done.
+    fn update_running(&mut self, e: &Event, running: &mut Running) -> bool {
+        let now: Instant = Instant::now();
+        let rows: usize = visible_lines(self.backend.size(), self.text_offset, self.font_size);
+        running.modifiers.event(e);
+        let modifiers: ModifierKey = running.modifiers;
+        if self.is_skip_text(e) { return false; }
+        if e.render_args().is_some() { running.flash = check_flash_every(now, &mut running.flash_start, self.flash_time); }
 
-            for word in old_message.split_whitespace() {
-                let word_len: usize = word.len();
-                let message_len: usize = new_message.len();
+        if let Some(typing) = running.typing.as_ref() {
+            // Like in ask, the key press that skips typing isn't given to the prompt.
+            let skipped: bool = self.skips_typing(e);
+            if !skipped && typing.typed(now) < typing.ends.len() { return false; }
+            running.typing = None;
+            if skipped { return false; }
+        }
 
-                if word_len > max_chars {
-                    if message_len > 0 {
-                        let word_vec = split_word(word, max_chars - (message_len + 1), max_chars);
-                        let mut word_iter = word_vec.iter();
-                        new_message_vec.push(format!("{} {}", new_message, word_iter.next().unwrap()));
-                        for continued_word in word_iter {
-                            new_message_vec.push(continued_word.to_string());
-                        }
-                        new_message = new_message_vec.pop().unwrap();
-                    } else {
-                        new_message_vec.append(&mut split_word(word, max_chars, max_chars));
-                    }
-                } else if message_len + word_len > max_chars {
-                    new_message_vec.push(new_message);
-                    new_message = String::from(word);
-                } else if message_len > 0 {
-                    new_message = format!("{} {}", new_message, word);
-                } else {
-                    new_message = String::from(word);
+        self.scroll = scrolled(self.scroll, scroll_amount(e, modifiers, rows), self.message.len(), rows);
+        match &mut running.stage {
+            Stage::Show { time, until } => {
+                if now >= *until.get_or_insert(now + *time) {
+                    self.events.push_back(TerminalEvent::MessageShown);
+                    return true;
+                }
+            }
+            Stage::Ask { editing } => {
+                let mut history: History = mem::take(&mut self.history);
+                let submitted: Option<Result<(), String>> = self.edit_input(e, modifiers, editing, &Prompt::new(&mut history));
+                self.history = history;
+
+                if let Some(Ok(())) = submitted {
+                    let input: String = editing.line.take();
+                    self.history.push(&input);
+                    if self.append_mode { self.append_lines(vec![format!("> {}", input)]); }
+                    self.input = input.clone();
+                    self.events.push_back(TerminalEvent::InputSubmitted(input));
+                    return true;
                 }
             }
-            if !new_message.is_empty() { new_message_vec.push(new_message); }
         }
-        self.message = new_message_vec;
+        false
+    }
+
+    // Works out what to draw for the running message or prompt, or for the message if nothing is running.
+    fn queue_frame(&self) -> QueueFrame {
+        let rows: usize = visible_lines(self.backend.size(), self.text_offset, self.font_size);
+        let running: Option<&Running> = self.running.as_ref();
+        let (message, position, marker, input, cursor): (Vec<String>, ScrollPosition, bool, Option<String>, Option<usize>) =
+            match (running.and_then(|running| running.typing.as_ref()), running) {
+                (Some(typing), _) => {
+                    let typed_message: Vec<String> = typed_lines(&self.message, &typing.ends, typing.typed(Instant::now()));
+                    let (message, position): (&[String], ScrollPosition) = view(&typed_message, rows, 0);
+                    (message.to_vec(), position, false, Some(self.input.clone()), None)
+                }
+                (None, Some(Running { stage: Stage::Ask { editing }, flash, .. })) => {
+                    let (message, position): (&[String], ScrollPosition) = view(&self.message, rows, self.scroll);
+                    let search: Option<String> = editing.recall.prompt(&editing.line);
+                    let cursor: Option<usize> = if *flash && search.is_none() { Some(editing.line.cursor()) } else { None };
+                    (message.to_vec(), position, true, Some(search.unwrap_or_else(|| String::from(editing.line.text()))), cursor)
+                }
+                _ => {
+                    let (message, position): (&[String], ScrollPosition) = view(&self.message, rows, self.scroll);
+                    (message.to_vec(), position, false, None, None)
+                }
+            };
+
+        QueueFrame {
+            bg_color: self.bg_color,
+            fg_color: self.fg_color,
+            scanlines: self.scanlines,
+            font_size: self.font_size,
+            text_offset: self.text_offset,
+            message,
+            position,
+            marker,
+            input,
+            cursor,
+        }
+    }
+
+    // Updates the selection from mouse events, and copies the selected text when the left mouse button is released.
+    fn drag_selection(&mut self, e: &Event, dragging: &mut Dragging, rows: usize) -> Result<(), Error> {
+        if let Some(mouse) = e.mouse_cursor_args() {
+            dragging.mouse = mouse;
+            if dragging.held { dragging.end = self.locate(mouse, rows)?; }
+        }
+
+        if let Some(Button::Mouse(MouseButton::Left)) = e.press_args() {
+            let here: (usize, usize) = self.locate(dragging.mouse, rows)?;
+            dragging.anchor = Some(here);
+            dragging.end = here;
+            dragging.held = true;
+        }
+
+        if let Some(Button::Mouse(MouseButton::Left)) = e.release_args() {
+            dragging.held = false;
+            if let Some(anchor) = dragging.anchor {
+                let text: String = selected_text(&self.message, anchor, dragging.end);
+                if !text.is_empty() { self.clipboard().set_text(&text); }
+            }
+        }
+        Ok(())
+    }
+
+    // Returns the line of the message and the byte index in that line closest to the given position in the window.
+    fn locate(&mut self, mouse: [f64; 2], rows: usize) -> Result<(usize, usize), Error> {
+        let (lines, position): (&[String], ScrollPosition) = view(&self.message, rows, self.scroll);
+        if lines.is_empty() { return Ok((0, 0)); }
+
+        let line_height: f64 = (self.font_size as f64) * 0.8;
+        let row: usize = (((mouse[1] - self.text_offset.1 - line_height * 0.1) / line_height).ceil().max(0.0) as usize).min(lines.len() - 1);
+        let line: String = lines[row].clone();
+        let x: f64 = mouse[0] - self.text_offset.0;
+
+        let mut index: usize = 0;
+        let mut previous: f64 = 0.0;
+        for (i, grapheme) in line.grapheme_indices(true) {
+            let end: usize = i + grapheme.len();
+            let width: f64 = self.backend.text_width(&line[..end], self.font_size)?;
+            if width >= x {
+                if width - x < x - previous { index = end; }
+                break;
+            }
+            index = end;
+            previous = width;
+        }
+        Ok((position.first + row, index))
+    }
+
+    // Adds text like completion candidates or an error to the end of the message, wrapped to fit the window, and returns the added lines.
+    fn append_note(&mut self, note: &str) -> Vec<String> {
+        let max_width: f64 = self.backend.size().width - self.text_offset.0 * 2.0;
+        let font_size: FontSize = self.font_size;
+        let backend: &mut B = &mut self.backend;
+
+        match wrap_text_by(note, max_width, |text| backend.text_width(text, font_size)) {
+            Ok(lines) => {
+                self.append_lines(lines.clone());
+                self.scroll = 0;
+                lines
+            }
+            Err(e) => {
+                self.stop_on_error(Err(e));
+                Vec::new()
+            }
+        }
+    }
+
+    // Removes the given lines of an earlier note from the message, then adds the given note if there is one, and returns its lines.
+    fn replace_note(&mut self, old: &[String], note: Option<&str>) -> Vec<String> {
+        if !old.is_empty() {
+            if let Some(start) = self.message.windows(old.len()).rposition(|lines| lines == old) {
+                self.message.drain(start..start + old.len());
+            }
+        }
+        note.map_or(Vec::new(), |note| self.append_note(note))
+    }
+
+    // Adds lines to the end of the message, dropping the oldest lines past the scrollback limit.
+    fn append_lines(&mut self, lines: Vec<String>) {
+        self.message.extend(lines);
+        let extra: usize = self.message.len().saturating_sub(self.scrollback);
+        self.message.drain(..extra);
+    }
+}
+
+// Finds the given font in the given resource folders and loads it into the backend.
+fn load_font_into<B: Backend>(backend: &mut B, resources: &ResourcePath, font: &FontSource) -> Result<(), Error> {
+    backend.load_font(&resources.resolve_font(font)?)
+}
+
+// Returns the next input from the input source if there is one, otherwise the next event from the backend.
+fn next_event<B: Backend>(backend: &mut B, input_source: &mut Option<Box<dyn InputSource>>) -> Option<Event> {
+    match input_source.as_mut().and_then(|source| source.next_input()) {
+        Some(input) => Some(Event::Input(input, None)),
+        None => backend.next_event(),
+    }
+}
+
+// Returns the line and the byte index of the end of each grapheme in the message, from the given line onwards.
+fn grapheme_ends(message: &[String], first_line: usize) -> Vec<(usize, usize)> {
+    message.iter().enumerate().skip(first_line)
+        .flat_map(|(i, line)| line.grapheme_indices(true).map(move |(j, grapheme)| (i, j + grapheme.len())))
+        .collect()
+}
+
+// Returns the lines of the message up to the given number of typed graphemes, with a block after the last one typed.
+fn typed_lines(message: &[String], ends: &[(usize, usize)], typed: usize) -> Vec<String> {
+    let (line, end): (usize, usize) = ends[typed.max(1) - 1];
+    let mut lines: Vec<String> = message[..line].to_vec();
+    lines.push(format!("{}[]", &message[line][..end]));
+    lines
+}
+
+// A message or prompt waiting in the terminal's queue.
+enum Queued {
+    Show(String, Duration),
+    Ask(String),
+}
+
+// The message or prompt the terminal is working on while it is driven by update.
+struct Running {
+    stage: Stage,
+    typing: Option<Typing>,
+    modifiers: ModifierKey,
+    flash_start: Instant,
+    flash: bool,
+}
+
+impl Running {
+    // Returns whether the running prompt is done typing out and is waiting for the user's input.
+    fn is_waiting(&self) -> bool {
+        self.typing.is_none() && matches!(self.stage, Stage::Ask { .. })
+    }
+}
+
+// What the running message or prompt does once its message is typed out.
+enum Stage {
+    Show { time: Duration, until: Option<Instant> },
+    Ask { editing: InputLine },
+}
+
+// The progress of typing out the running message.
+struct Typing {
+    start: Instant,
+    type_time: Duration,
+    ends: Vec<(usize, usize)>,
+}
+
+impl Typing {
+    // Returns how many of the message's characters are typed out at the given time.
+    fn typed(&self, now: Instant) -> usize {
+        typed_count(now.saturating_duration_since(self.start), self.type_time, self.ends.len())
+    }
+}
+
+// What to draw for the message or prompt the terminal is working on while it is driven by update.
+struct QueueFrame {
+    bg_color: Color,
+    fg_color: Color,
+    scanlines: bool,
+    font_size: FontSize,
+    text_offset: (f64, f64),
+    message: Vec<String>,
+    position: ScrollPosition,
+    marker: bool,
+    input: Option<String>,
+    cursor: Option<usize>,
+}
+
+impl QueueFrame {
+    // Returns the frame to draw.
+    fn frame(&self) -> Frame<'_> {
+        Frame {
+            bg_color: self.bg_color,
+            fg_color: self.fg_color,
+            scanlines: self.scanlines,
+            font_size: self.font_size,
+            text_offset: self.text_offset,
+            contents: Contents::Text {
+                message: &self.message,
+                marker: self.marker,
+                input: self.input.as_deref(),
+                cursor: self.cursor,
+                scroll: Some(self.position),
+                selection: None,
+                highlight: None,
+                status: None,
+            },
+        }
+    }
+}
+
+// The mouse position and the selection the user is dragging out, as lines of the message and byte indices in those lines.
+#[derive(Default)]
+struct Dragging {
+    mouse: [f64; 2],
+    held: bool,
+    anchor: Option<(usize, usize)>,
+    end: (usize, usize),
+}
+
+impl Dragging {
+    // Returns the part of the selection that is in the given view, relative to the first line of the view.
+    fn shown(&self, position: ScrollPosition) -> Option<Selection> {
+        let anchor: (usize, usize) = self.anchor?;
+        let (start, end): ((usize, usize), (usize, usize)) = if anchor <= self.end { (anchor, self.end) } else { (self.end, anchor) };
+        let last: usize = position.first + position.visible;
+        if start == end || end.0 < position.first || start.0 >= last { return None; }
+
+        Some(Selection {
+            start: if start.0 < position.first { (0, 0) } else { (start.0 - position.first, start.1) },
+            end: if end.0 >= last { (position.visible - 1, usize::MAX) } else { (end.0 - position.first, end.1) },
+        })
     }
+}
+
+// Returns the text of the message between the two positions, with a newline between each line.
+fn selected_text(message: &[String], a: (usize, usize), b: (usize, usize)) -> String {
+    let (start, end): ((usize, usize), (usize, usize)) = if a <= b { (a, b) } else { (b, a) };
 
-    // Determines the max number of characters based on window and font size.
-    fn get_max_characters(&self) -> usize {
-        ((self.window.window.size().width / self.font_size as f64) * 2.15) as usize
+    let mut text: String = String::new();
+    for (i, line) in message.iter().enumerate().take(end.0 + 1).skip(start.0) {
+        let from: usize = if i == start.0 { start.1.min(line.len()) } else { 0 };
+        let to: usize = if i == end.0 { end.1.min(line.len()) } else { line.len() };
+        if i > start.0 { text.push('\n'); }
+        text.push_str(&line[from..to.max(from)]);
     }
+    text
+}
+
+// Returns whether the given key and modifier keys paste from the clipboard.
+fn is_paste(key: Key, modifiers: ModifierKey) -> bool {
+    (key == Key::V && modifiers.contains(ModifierKey::CTRL)) || (key == Key::Insert && modifiers.contains(ModifierKey::SHIFT))
+}
+
+// The settings of a single prompt, used while waiting for the user's input.
+struct Prompt<'a> {
+    history: &'a mut History,
+    completer: Option<&'a dyn Completer>,
+    secret: bool,
+    mask: Option<char>,
+    rules: Option<&'a InputRules<'a>>,
+    timeout: Option<(Duration, &'a str)>,
+}
+
+impl<'a> Prompt<'a> {
+    // Creates a prompt that remembers inputs in the given history, without any other settings.
+    fn new(history: &'a mut History) -> Prompt<'a> {
+        Prompt { history, completer: None, secret: false, mask: None, rules: None, timeout: None }
+    }
+}
+
+// The input line of a prompt, along with searching the history and completing words in it.
+struct InputLine {
+    line: LineEditor,
+    recall: Recall,
+    tabbed: bool,
+}
+
+impl InputLine {
+    // Creates an input line that edits the given line.
+    fn new(line: LineEditor) -> InputLine {
+        InputLine { line, recall: Recall::new(), tabbed: false }
+    }
+}
+
+// Inserts text into the line, leaving out anything the rules don't allow.
+fn insert(line: &mut LineEditor, text: &str, rules: Option<&InputRules>) {
+    match rules {
+        Some(rules) => line.insert(&rules.fit(line.text(), text)),
+        None => line.insert(text),
+    }
+}
+
+// Passes the given text to the recall if it is searching the history, or inserts it into the line otherwise.
+fn recall_or_insert(line: &mut LineEditor, text: &str, secret: bool, recall: &mut Recall, history: &History, rules: Option<&InputRules>) {
+    if !secret && recall.handle_text(text, history, line) {
+        refit(line, rules);
+    } else {
+        insert(line, text, rules);
+    }
+}
+
+// Leaves out the parts of the line that break the given rules, after whole text was put into it by recall or completion.
+fn refit(line: &mut LineEditor, rules: Option<&InputRules>) {
+    if let Some(rules) = rules {
+        let fitted: String = rules.fit("", line.text());
+        if fitted != line.text() { line.set_text(&fitted); }
+    }
+}
+
+// Completes the line with the candidates the completer gives, and returns the candidates if they should be listed.
+// The common prefix of the candidates is filled in, followed by a space if there is only one candidate.
+// Candidates are listed if there are several of them and Tab was already pressed.
+fn complete(completer: &dyn Completer, line: &mut LineEditor, tabbed: bool) -> Option<Vec<String>> {
+    let completion: Completion = completer.complete(line.text(), line.cursor());
+    match completion.candidates.len() {
+        0 => None,
+        1 => {
+            line.replace_before_cursor(completion.start, &format!("{} ", completion.common_prefix()));
+            None
+        }
+        _ if tabbed => Some(completion.candidates),
+        _ => {
+            line.replace_before_cursor(completion.start, completion.common_prefix());
+            None
+        }
+    }
+}
+
+// Returns the lines of the message that fit in the given number of rows after scrolling up by the given number of lines,
+// along with where those lines are in the message.
+fn view(message: &[String], rows: usize, scroll: usize) -> (&[String], ScrollPosition) {
+    let end: usize = message.len() - scroll.min(message.len());
+    let first: usize = end.saturating_sub(rows);
+    (&message[first..end], ScrollPosition { first, visible: end - first, total: message.len() })
+}
+
+// Returns the scroll offset closest to the given one that keeps the given line of the message in view.
+fn following(scroll: usize, line: usize, lines: usize, rows: usize) -> usize {
+    let end: usize = lines - scroll.min(lines);
+    if line >= end {
+        lines - line - 1
+    } else if line + rows < end {
+        lines - line - rows
+    } else {
+        scroll
+    }
+}
+
+// Returns how many lines the given event scrolls the view up by, or a negative number if it scrolls the view down.
+fn scroll_amount(e: &Event, modifiers: ModifierKey, rows: usize) -> isize {
+    let page: isize = rows.saturating_sub(1).max(1) as isize;
+    let shift: bool = modifiers.contains(ModifierKey::SHIFT);
+
+    let mut amount: isize = 0;
+    e.button(|button_args| {
+        if let (ButtonState::Press, Button::Keyboard(key)) = (button_args.state, button_args.button) {
+            amount = match key {
+                Key::PageUp => page,
+                Key::PageDown => -page,
+                Key::Up if shift => 1,
+                Key::Down if shift => -1,
+                _ => 0,
+            };
+        }
+    });
+    e.mouse_scroll(|scroll| {
+        // Horizontal scrolling has a vertical amount of zero, whose sign would still be positive.
+        if scroll[1] != 0.0 {
+            amount = scroll[1].signum() as isize * WHEEL_LINES;
+        }
+    });
+    amount
+}
+
+// Returns the scroll offset after scrolling by the given amount, kept between the bottom and the top of the message.
+fn scrolled(scroll: usize, amount: isize, lines: usize, rows: usize) -> usize {
+    (scroll as isize + amount).clamp(0, lines.saturating_sub(rows) as isize) as usize
 }
@@ -0,0 +1,155 @@
+use image::RgbaImage;
+use piston_window::{Context, Size};
+use std::{env, fs, path::{Path, PathBuf}};
+
+use crate::{draw::*, font::FontSource, headless::*, resources::ResourcePath, Error};
+
+/// Set this environment variable to save rendered frames as the new snapshots instead of comparing against them.
+pub const UPDATE_SNAPSHOTS: &str = "SIMPLETERM_UPDATE_SNAPSHOTS";
+
+/// The folder snapshots are stored in by default, relative to the crate root.
+pub const SNAPSHOT_DIR: &str = "tests/snapshots";
+
+/// How far a rendered frame may differ from its snapshot before the comparison fails.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tolerance {
+    /// The largest difference allowed in any color channel before a pixel counts as different.
+    pub channel: u8,
+    /// How many pixels may be different.
+    pub pixels: usize,
+}
+
+impl Default for Tolerance {
+    fn default() -> Tolerance {
+        Tolerance { channel: 2, pixels: 0 }
+    }
+}
+
+/// Creates a buffer of the given size and a glyph cache for the given font, then lets the given closure draw into it.
+/// The font is looked up in the default resource folders.
+///
+/// ```
+/// # use piston_window::clear;
+/// # use simpleterm::{draw::*, testing::*, text::*, TEXT_OFFSET};
+/// let buffer = render_with((320, 240), "LeagueSpartan-Regular.ttf", |win_size, glyphs, context, graphics| {
+///     clear(DARK_GREY, graphics);
+///     draw_background(win_size, DARK_GREY, GOLD, false, context, graphics);
+///     draw_input_marker(win_size, TEXT_OFFSET, glyphs, 24, GOLD, context, graphics)
+/// }).unwrap();
+/// assert_eq!(buffer.pixel(12, 12), [255, 166, 26, 255]);
+/// ```
+pub fn render_with<S, F>(size: (u32, u32), font: S, draw: F) -> Result<FrameBuffer, Error>
+where
+    S: Into<FontSource>,
+    F: FnOnce(Size, &mut HeadlessGlyphs, Context, &mut FrameBuffer) -> Result<(), Error>,
+{
+    let mut buffer: FrameBuffer = FrameBuffer::new(size.0, size.1);
+    let mut glyphs: HeadlessGlyphs = load_headless_font(&ResourcePath::default().resolve_font(&font.into())?)?;
+    let context: Context = Context::new_abs(size.0 as f64, size.1 as f64);
+
+    draw(size.into(), &mut glyphs, context, &mut buffer)?;
+    Ok(buffer)
+}
+
+/// Draws the given frame into a new buffer of the given size, using the given font.
+///
+/// ```
+/// # use simpleterm::{draw::*, testing::*, text::*, TEXT_OFFSET};
+/// let message: Vec<String> = vec![String::from("Hello!")];
+/// let frame: Frame = Frame {
+///     bg_color: DARK_GREY,
+///     fg_color: GOLD,
+///     scanlines: true,
+///     font_size: 24,
+///     text_offset: TEXT_OFFSET,
+///     contents: Contents::Text { message: &message, marker: true, input: Some(""), cursor: Some(0), scroll: None, selection: None, highlight: None, status: None },
+/// };
+/// let buffer = render_frame(&frame, (320, 240), "LeagueSpartan-Regular.ttf").unwrap();
+/// assert_eq!((buffer.width, buffer.height), (320, 240));
+/// ```
+pub fn render_frame<S: Into<FontSource>>(frame: &Frame, size: (u32, u32), font: S) -> Result<FrameBuffer, Error> {
+    render_with(size, font, |win_size, glyphs, context, graphics| {
+        draw_frame(frame, win_size, glyphs, context, graphics)
+    })
+}
+
+/// Compares the buffer against the snapshot with the given name in SNAPSHOT_DIR, using the default tolerance.
+/// Panics with a description of the differences if they don't match.
+///
+/// When the UPDATE_SNAPSHOTS environment variable is set, the buffer is saved as the new snapshot instead.
+pub fn assert_snapshot(name: &str, buffer: &FrameBuffer) {
+    assert_snapshot_in(Path::new(SNAPSHOT_DIR), name, buffer, Tolerance::default());
+}
+
+/// Compares the buffer against the snapshot with the given name in the given folder, using the given tolerance.
+/// Panics with a description of the differences if they don't match.
+pub fn assert_snapshot_in(dir: &Path, name: &str, buffer: &FrameBuffer, tolerance: Tolerance) {
+    if let Err(message) = check_snapshot(dir, name, buffer, tolerance) {
+        panic!("{}", message);
+    }
+}
+
+/// Compares the buffer against the snapshot with the given name in the given folder.
+/// If they don't match, the buffer is saved next to the snapshot as "name.actual.png" and a description of the differences is returned.
+///
+/// ```
+/// # use std::env;
+/// # use simpleterm::{headless::FrameBuffer, testing::*};
+/// let buffer: FrameBuffer = FrameBuffer::new(8, 8);
+/// assert!(check_snapshot(&env::temp_dir(), "simpleterm-missing-snapshot", &buffer, Tolerance::default()).is_err());
+/// ```
+pub fn check_snapshot(dir: &Path, name: &str, buffer: &FrameBuffer, tolerance: Tolerance) -> Result<(), String> {
+    let path: PathBuf = dir.join(format!("{}.png", name));
+    if env::var_os(UPDATE_SNAPSHOTS).is_some() {
+        return save_png(buffer, &path);
+    }
+
+    let expected: RgbaImage = match image::open(&path) {
+        Ok(image) => image.to_rgba8(),
+        Err(e) => return Err(format!("Could not open snapshot {}: {}. Set {}=1 to create it.", path.display(), e, UPDATE_SNAPSHOTS)),
+    };
+
+    let failure: Option<String> = if expected.dimensions() != (buffer.width, buffer.height) {
+        Some(format!(
+            "Snapshot {} is {}x{}, but the frame is {}x{}.",
+            path.display(), expected.width(), expected.height(), buffer.width, buffer.height,
+        ))
+    } else {
+        let mut different: usize = 0;
+        let mut largest: u8 = 0;
+        for (actual, expected) in buffer.pixels().chunks(4).zip(expected.as_raw().chunks(4)) {
+            let diff: u8 = actual.iter().zip(expected).map(|(a, e)| a.abs_diff(*e)).max().unwrap_or(0);
+            if diff > tolerance.channel { different += 1; }
+            largest = largest.max(diff);
+        }
+
+        if different > tolerance.pixels {
+            Some(format!(
+                "{} pixels differ from snapshot {} (up to {} per channel), but only {} may differ by more than {}.",
+                different, path.display(), largest, tolerance.pixels, tolerance.channel,
+            ))
+        } else {
+            None
+        }
+    };
+
+    match failure {
+        Some(message) => {
+            let actual_path: PathBuf = dir.join(format!("{}.actual.png", name));
+            save_png(buffer, &actual_path)?;
+            Err(format!("{} The rendered frame was saved to {}.", message, actual_path.display()))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Saves the buffer as a PNG image at the given path, creating any missing folders.
+pub fn save_png(buffer: &FrameBuffer, path: &Path) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Could not create {}: {}", parent.display(), e))?;
+    }
+
+    let image: RgbaImage = RgbaImage::from_raw(buffer.width, buffer.height, buffer.pixels().to_vec())
+        .ok_or_else(|| String::from("The buffer does not match its own size"))?;
+    image.save(path).map_err(|e| format!("Could not save {}: {}", path.display(), e))
+}
@@ -1,7 +1,8 @@
 use piston_window::{*, types::{Color, FontSize}};
-use std::{path::Path, {time::Duration, time::Instant}};
+use std::{convert::Infallible, time::{Duration, Instant}};
+use unicode_segmentation::UnicodeSegmentation;
 
-use crate::FLASH_TIME;
+use crate::{font::*, Error, FLASH_TIME};
 
 /// <span style="color:#DB143D; text-shadow: 1px 0.5px #555">█</span>
 pub const CRIMSON: Color =      [0.86, 0.08, 0.24, 1.0];
@@ -59,14 +60,15 @@ impl TermColor for Color {
     }
 }
 
-/// Returns the Glyph cache generated from the given font file opened in the given PistonWindow.
-pub fn load_font(window: &mut PistonWindow, name: &str) -> Glyphs {
-    let resources: &Path = Path::new("resources");
-    window.load_font(resources.join(name)).unwrap()
+/// Returns the Glyph cache generated from the given font opened in the given PistonWindow.
+pub fn load_font(window: &mut PistonWindow, font: &FontSource) -> Result<Glyphs, Error> {
+    Ok(Glyphs::from_font(read_font(font)?, window.create_texture_context(), TextureSettings::new()))
 }
 
 /// Returns a vector of strings corresponding to a word split up at the given number of characters.
 /// first_split may be smaller than rest_split to allow the first part of a word to fit on a line with previous words.
+/// 
+/// Characters are counted as grapheme clusters, so accented letters and other multi-codepoint characters are never split apart.
 /// ```
 /// # use simpleterm::text::*;
 /// let long_word: &str = "supercalifragilisticexpialidocious";
@@ -79,6 +81,8 @@ pub fn load_font(window: &mut PistonWindow, name: &str) -> Glyphs {
 ///         String::from("lidocious"),
 ///     )
 /// );
+/// assert_eq!(split_word("cafe\u{301}s\u{301}", 4, 4), vec!(String::from("cafe\u{301}"), String::from("s\u{301}")));
+/// assert_eq!(split_word("東京都庁舎", 2, 2), vec!(String::from("東京"), String::from("都庁"), String::from("舎")));
 /// ```
 pub fn split_word(x: &str, first_split: usize, rest_split: usize) -> Vec<String> {
     let mut result: Vec<String> = Vec::new();
@@ -86,23 +90,23 @@ pub fn split_word(x: &str, first_split: usize, rest_split: usize) -> Vec<String>
     let mut do_first: bool = true;
     let mut count: usize = 0;
     let mut current_string: String = String::default();
-    for c in x.chars() {
+    for c in x.graphemes(true) {
         if do_first {
             if count >= first_split {
                 result.push(current_string);
-                current_string = format!("{}", c);
+                current_string = String::from(c);
                 do_first = false;
                 count = 1;
             } else {
-                current_string.push(c);
+                current_string.push_str(c);
                 count += 1;
             }
         } else if count >= rest_split {
             result.push(current_string);
-            current_string = format!("{}", c);
+            current_string = String::from(c);
             count = 1;
         } else {
-            current_string.push(c);
+            current_string.push_str(c);
             count += 1;
         }
     }
@@ -111,6 +115,111 @@ pub fn split_word(x: &str, first_split: usize, rest_split: usize) -> Vec<String>
     result
 }
 
+/// Returns the number of characters in the given string, counting each grapheme cluster as one character.
+/// ```
+/// # use simpleterm::text::*;
+/// assert_eq!(text_length("naïve"), 5);
+/// assert_eq!(text_length("nai\u{308}ve"), 5);
+/// assert_eq!(text_length("日本語"), 3);
+/// ```
+pub fn text_length(x: &str) -> usize {
+    x.graphemes(true).count()
+}
+
+/// Splits a message into lines of at most the given number of characters, breaking at spaces where possible.
+/// Newlines in the message always start a new line, and words longer than a line are split between characters.
+/// ```
+/// # use simpleterm::text::*;
+/// assert_eq!(
+///     wrap_text("Crème brûlée für alle\nok", 12),
+///     vec!(String::from("Crème brûlée"), String::from("für alle"), String::from("ok")),
+/// );
+/// assert_eq!(
+///     wrap_text("東京 日本語のテキスト", 4),
+///     vec!(String::from("東京 日"), String::from("本語のテ"), String::from("キスト")),
+/// );
+/// ```
+pub fn wrap_text(message: &str, max_chars: usize) -> Vec<String> {
+    let wrapped: Result<Vec<String>, Infallible> = wrap_text_by(message, max_chars as f64, |text| Ok(text_length(text) as f64));
+    match wrapped {
+        Ok(lines) => lines,
+        Err(never) => match never {},
+    }
+}
+
+/// Splits a message into lines no wider than the given width, as measured by the given function.
+/// This lets proportional fonts be wrapped by the actual width of their glyphs.
+/// Newlines in the message always start a new line, and words wider than a line are split between characters.
+/// Returns the first error the measuring function returns.
+/// ```
+/// # use simpleterm::{draw::*, headless::*, font::*, text::*, Error};
+/// let mut glyphs: HeadlessGlyphs = load_headless_font(&EmbeddedFont::LeagueSpartan.into()).unwrap();
+/// let lines: Vec<String> = wrap_text_by("iiiiii WWWWWW iiiiii", 100.0, |text| measure_text(&mut glyphs, text, 24)).unwrap();
+/// assert_eq!(lines.len(), 3);
+/// for line in lines.iter() {
+///     assert!(measure_text(&mut glyphs, line, 24).unwrap() <= 100.0);
+/// }
+/// ```
+pub fn wrap_text_by<F, E>(message: &str, max_width: f64, mut width: F) -> Result<Vec<String>, E>
+where
+    F: FnMut(&str) -> Result<f64, E>,
+{
+    let mut lines: Vec<String> = Vec::new();
+
+    for paragraph in message.split('\n') {
+        let mut line: String = String::new();
+
+        for word in paragraph.split_whitespace() {
+            let joined: String = if line.is_empty() { String::from(word) } else { format!("{} {}", line, word) };
+
+            if width(&joined)? <= max_width {
+                line = joined;
+            } else if !line.is_empty() && width(word)? <= max_width {
+                lines.push(line);
+                line = String::from(word);
+            } else {
+                let mut rest: &str = word;
+                while !rest.is_empty() {
+                    let start: String = if line.is_empty() { String::new() } else { format!("{} ", line) };
+                    let mut fit: usize = longest_fit(&start, rest, max_width, &mut width)?;
+
+                    if fit == 0 {
+                        if !line.is_empty() {
+                            lines.push(line);
+                            line = String::new();
+                            continue;
+                        }
+                        fit = rest.graphemes(true).next().map_or(rest.len(), str::len);
+                    }
+
+                    line = format!("{}{}", start, &rest[..fit]);
+                    rest = &rest[fit..];
+                    if !rest.is_empty() {
+                        lines.push(line);
+                        line = String::new();
+                    }
+                }
+            }
+        }
+        if !line.is_empty() { lines.push(line); }
+    }
+
+    Ok(lines)
+}
+
+// Returns the length in bytes of the longest run of whole graphemes at the start of text that still fits in max_width after start.
+fn longest_fit<F, E>(start: &str, text: &str, max_width: f64, width: &mut F) -> Result<usize, E>
+where
+    F: FnMut(&str) -> Result<f64, E>,
+{
+    let mut fit: usize = 0;
+    for (i, grapheme) in text.grapheme_indices(true) {
+        if width(&format!("{}{}", start, &text[..i + grapheme.len()]))? > max_width { break; }
+        fit = i + grapheme.len();
+    }
+    Ok(fit)
+}
+
 /// Determines if enough time has passed since the last flash toggle. If so, save the current time and toggle the current flash state.
 /// ```
 /// # use std::{thread, time::{Duration, Instant}};
@@ -120,15 +229,74 @@ pub fn split_word(x: &str, first_split: usize, rest_split: usize) -> Vec<String>
 /// assert!(check_flash(Instant::now(), &mut start));
 /// ```
 pub fn check_flash(now: Instant, then: &mut Instant) -> bool {
+    check_flash_every(now, then, FLASH_TIME)
+}
+
+/// Works like check_flash, but toggles the flash state every given period instead of every FLASH_TIME.
+/// ```
+/// # use std::{convert::Infallible, time::{Duration, Instant}};
+/// # use simpleterm::text::*;
+/// let start: Instant = Instant::now();
+/// let mut then: Instant = start;
+/// assert!(!check_flash_every(start + Duration::from_millis(50), &mut then, Duration::from_millis(100)));
+/// assert!(check_flash_every(start + Duration::from_millis(150), &mut then, Duration::from_millis(100)));
+/// ```
+pub fn check_flash_every(now: Instant, then: &mut Instant, period: Duration) -> bool {
     let time_since: Duration = now.duration_since(*then);
-    if time_since > (FLASH_TIME * 2) {
+    if time_since > (period * 2) {
         *then = now;
         true
     } else {
-        time_since > FLASH_TIME
+        time_since > period
     }
 }
 
+/// Returns how many of a message's characters should be typed out after the given time, typing one every type time.
+/// The first character appears right away, and a zero type time types the whole message at once.
+/// ```
+/// # use std::time::Duration;
+/// # use simpleterm::text::*;
+/// let type_time: Duration = Duration::from_millis(20);
+/// assert_eq!(typed_count(Duration::from_millis(0), type_time, 10), 1);
+/// assert_eq!(typed_count(Duration::from_millis(45), type_time, 10), 3);
+/// assert_eq!(typed_count(Duration::from_secs(5), type_time, 10), 10);
+/// assert_eq!(typed_count(Duration::from_millis(0), Duration::from_millis(0), 10), 10);
+/// ```
+pub fn typed_count(elapsed: Duration, type_time: Duration, total: usize) -> usize {
+    if type_time.as_nanos() == 0 { return total; }
+
+    let typed: u128 = elapsed.as_nanos() / type_time.as_nanos() + 1;
+    typed.min(total as u128) as usize
+}
+
+/// Returns the type time that types the given number of characters per second.
+/// A rate that isn't above zero gives the longest possible type time, so only the first character is ever typed,
+/// and an infinite rate gives a zero type time, so the whole message is shown at once.
+/// ```
+/// # use std::time::Duration;
+/// # use simpleterm::text::*;
+/// assert_eq!(type_time_for(50.0), Duration::from_millis(20));
+/// assert_eq!(type_time_for(0.0), Duration::MAX);
+/// assert_eq!(type_time_for(f64::INFINITY), Duration::from_secs(0));
+/// ```
+pub fn type_time_for(chars_per_second: f64) -> Duration {
+    Duration::try_from_secs_f64(1.0 / chars_per_second).unwrap_or(Duration::MAX)
+}
+
+/// Returns how many lines of text fit between the top of the terminal and the input line,
+/// for a window of the given size, text offset, and font size. At least one line always fits.
+/// ```
+/// # use simpleterm::{text::*, TEXT_OFFSET};
+/// assert_eq!(visible_lines((800, 600).into(), TEXT_OFFSET, 32), 20);
+/// assert_eq!(visible_lines((800, 100).into(), TEXT_OFFSET, 32), 1);
+/// ```
+pub fn visible_lines(win_size: Size, text_offset: (f64, f64), font_size: FontSize) -> usize {
+    let line_height: f64 = (font_size as f64) * 0.8;
+    let text_height: f64 = win_size.height - text_offset.1 * 2.0;
+
+    if text_height > 0.0 && line_height > 0.0 { (text_height / line_height) as usize + 1 } else { 1 }
+}
+
 /// Determines the top left corner of the given art in the given window, in order for the art to be centered.
 /// ```
 /// # use simpleterm::{art::*, text::*};
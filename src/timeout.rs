@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+/// The result of a prompt that gives up waiting after a while: either the user's answer, or the default answer if time ran out.
+///
+/// ```
+/// # use simpleterm::timeout::Timed;
+/// let answer: Timed<String> = Timed::TimedOut(String::from("guest"));
+/// assert!(answer.timed_out());
+/// assert_eq!(answer.into_inner(), "guest");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Timed<T> {
+    /// The user answered before time ran out.
+    Answered(T),
+    /// Time ran out, so this is the default answer.
+    TimedOut(T),
+}
+
+impl<T> Timed<T> {
+    /// Returns whether time ran out before the user answered.
+    pub fn timed_out(&self) -> bool {
+        matches!(self, Timed::TimedOut(_))
+    }
+
+    /// Returns the answer, whether the user gave it or it is the default.
+    pub fn into_inner(self) -> T {
+        match self {
+            Timed::Answered(answer) | Timed::TimedOut(answer) => answer,
+        }
+    }
+}
+
+/// Returns the text of a countdown showing how many seconds are left, rounded up.
+/// ```
+/// # use std::time::Duration;
+/// # use simpleterm::timeout::countdown;
+/// assert_eq!(countdown(Duration::from_millis(4200)), "5s");
+/// assert_eq!(countdown(Duration::from_secs(0)), "0s");
+/// ```
+pub fn countdown(remaining: Duration) -> String {
+    format!("{}s", remaining.as_millis().div_ceil(1000))
+}
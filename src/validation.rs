@@ -0,0 +1,82 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+// A check the input has to pass, which returns an error to show the user if it doesn't.
+type Validator<'a> = Box<dyn Fn(&str) -> Result<(), String> + 'a>;
+
+/// Limits what the user can type into a prompt, and checks their input before it is accepted.
+///
+/// Characters that aren't allowed and characters past the maximum length are left out as the user types.
+/// If the input breaks the rules when the user presses Enter, the error is shown below the message and the user stays on the prompt.
+///
+/// ```
+/// # use simpleterm::validation::InputRules;
+/// let rules: InputRules = InputRules::new()
+///     .max_length(4)
+///     .allowed(|c| c.is_ascii_digit())
+///     .validator(|input| if input.starts_with('0') { Err(String::from("no leading zeroes")) } else { Ok(()) });
+///
+/// assert_eq!(rules.fit("1", "2a34-5"), "234");
+/// assert_eq!(rules.check("12345"), Err(String::from("at most 4 characters allowed")));
+/// assert_eq!(rules.check("12a"), Err(String::from("'a' is not allowed")));
+/// assert_eq!(rules.check("0123"), Err(String::from("no leading zeroes")));
+/// assert_eq!(rules.check("1234"), Ok(()));
+/// ```
+#[derive(Default)]
+pub struct InputRules<'a> {
+    max_length: Option<usize>,
+    allowed: Option<Box<dyn Fn(char) -> bool + 'a>>,
+    validator: Option<Validator<'a>>,
+}
+
+impl<'a> InputRules<'a> {
+    /// Creates rules that allow any input.
+    pub fn new() -> InputRules<'a> {
+        InputRules::default()
+    }
+
+    /// Sets the most characters the input can have.
+    pub fn max_length(mut self, length: usize) -> InputRules<'a> {
+        self.max_length = Some(length);
+        self
+    }
+
+    /// Sets which characters can be typed.
+    pub fn allowed<F: Fn(char) -> bool + 'a>(mut self, allowed: F) -> InputRules<'a> {
+        self.allowed = Some(Box::new(allowed));
+        self
+    }
+
+    /// Sets the check the input has to pass when the user presses Enter. An error is shown to the user.
+    pub fn validator<F: Fn(&str) -> Result<(), String> + 'a>(mut self, validator: F) -> InputRules<'a> {
+        self.validator = Some(Box::new(validator));
+        self
+    }
+
+    /// Returns the part of the given text that can be added to the given line: the allowed characters, up to the maximum length.
+    pub fn fit(&self, line: &str, text: &str) -> String {
+        let allowed: String = match &self.allowed {
+            Some(allowed) => text.chars().filter(|c| allowed(*c)).collect(),
+            None => String::from(text),
+        };
+
+        match self.max_length {
+            Some(max_length) => allowed.graphemes(true).take(max_length.saturating_sub(line.graphemes(true).count())).collect(),
+            None => allowed,
+        }
+    }
+
+    /// Returns an error if the input is too long, has characters that aren't allowed, or doesn't pass the validator.
+    pub fn check(&self, input: &str) -> Result<(), String> {
+        if let Some(max_length) = self.max_length.filter(|max_length| input.graphemes(true).count() > *max_length) {
+            return Err(format!("at most {} characters allowed", max_length));
+        }
+        if let Some(c) = self.allowed.as_ref().and_then(|allowed| input.chars().find(|c| !allowed(*c))) {
+            return Err(format!("'{}' is not allowed", c));
+        }
+
+        match &self.validator {
+            Some(validator) => validator(input),
+            None => Ok(()),
+        }
+    }
+}